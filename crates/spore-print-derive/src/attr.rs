@@ -0,0 +1,350 @@
+// crates/spore-print-derive/src/attr.rs
+
+//! Parsing of the `#[spore(...)]` attributes shared by the `SporePrint` and `SporeParse` derives.
+
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, LitStr, Token, WherePredicate};
+
+/// Attributes on the struct or enum being derived.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// `rename = "..."`: the type name printed instead of the Rust name.
+    pub(crate) rename: Option<String>,
+    /// `rename_all = "..."`: the case of the field names of a struct, or of the variant names of an enum.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// `opaque`: the value is printed as the marker `<Name>`, without reading it. Required for unions.
+    pub(crate) opaque: bool,
+    /// `bound = "..."`: the `where` predicates of the generated implementation, replacing the inferred ones.
+    pub(crate) bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    /// `crate = "path"`: the path of the `spore_print` crate in generated code, for use through a facade.
+    pub(crate) krate: Option<syn::Path>,
+}
+
+/// Attributes on an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    /// `rename = "..."`: the variant name printed instead of the Rust name.
+    pub(crate) rename: Option<String>,
+    /// `rename_all = "..."`: the case of the field names of the variant.
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+/// Attributes on a struct or variant field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `skip`: the field is not printed, and is filled with `Default::default()` when parsing.
+    pub(crate) skip: bool,
+    /// `rename = "..."`: the field name printed instead of the Rust name.
+    pub(crate) rename: Option<String>,
+    /// `with = "path"`: a `fn(&T, &mut dyn fmt::Write) -> fmt::Result` printing the field.
+    pub(crate) with: Option<syn::Path>,
+    /// `parse_with = "path"`: a `fn(SporeValue) -> Result<T, SporeParseError>` reading the field back.
+    pub(crate) parse_with: Option<syn::Path>,
+    /// `redact` or `redact = "hash"`: the field is printed as a redaction marker.
+    pub(crate) redact: Option<Redaction>,
+}
+
+/// How a `#[spore(redact)]` field is printed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Redaction {
+    /// `<redacted>`, without reading the field.
+    Placeholder,
+    /// `<redacted:xxxxxxxx>`, with a digest of the spore print of the field.
+    Hash,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = ContainerAttrs::default();
+        for_each_spore_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                set_once(&meta, &mut result.rename, parse_name(&meta)?)
+            } else if meta.path.is_ident("rename_all") {
+                set_once(&meta, &mut result.rename_all, RenameRule::parse(&meta)?)
+            } else if meta.path.is_ident("bound") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let bound = lit.parse_with(Punctuated::parse_terminated)?;
+                set_once(&meta, &mut result.bound, bound)
+            } else if meta.path.is_ident("crate") {
+                set_once(&meta, &mut result.krate, parse_path(&meta)?)
+            } else if meta.path.is_ident("opaque") {
+                if result.opaque {
+                    return Err(meta.error("duplicate `opaque` attribute"));
+                }
+                result.opaque = true;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown container attribute, expected `rename`, `rename_all`, `opaque`, `bound` or `crate`",
+                ))
+            }
+        })?;
+        Ok(result)
+    }
+
+    /// Returns the printed name of the type named `ident`.
+    pub(crate) fn name(&self, ident: &syn::Ident) -> String {
+        self.rename.clone().unwrap_or_else(|| ident.to_string())
+    }
+
+    /// Returns the path through which generated code reaches the `spore_print` crate.
+    pub(crate) fn krate(&self) -> syn::Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(::spore_print))
+    }
+}
+
+impl VariantAttrs {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = VariantAttrs::default();
+        for_each_spore_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                set_once(&meta, &mut result.rename, parse_name(&meta)?)
+            } else if meta.path.is_ident("rename_all") {
+                set_once(&meta, &mut result.rename_all, RenameRule::parse(&meta)?)
+            } else {
+                Err(meta.error("unknown variant attribute, expected `rename` or `rename_all`"))
+            }
+        })?;
+        Ok(result)
+    }
+
+    /// Returns the printed name of the variant named `ident`, applying the enum's `rename_all` rule.
+    pub(crate) fn name(&self, ident: &syn::Ident, rule: Option<RenameRule>) -> String {
+        match (&self.rename, rule) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => with_raw_prefix(ident, |name| rule.apply_to_variant(name)),
+            (None, None) => ident.to_string(),
+        }
+    }
+}
+
+impl FieldAttrs {
+    pub(crate) fn from_field(field: &syn::Field) -> syn::Result<Self> {
+        let mut result = FieldAttrs::default();
+        for_each_spore_meta(&field.attrs, |meta| {
+            if meta.path.is_ident("skip") {
+                if result.skip {
+                    return Err(meta.error("duplicate `skip` attribute"));
+                }
+                result.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                if field.ident.is_none() {
+                    return Err(meta.error("`rename` is only allowed on named fields"));
+                }
+                set_once(&meta, &mut result.rename, parse_name(&meta)?)
+            } else if meta.path.is_ident("with") {
+                set_once(&meta, &mut result.with, parse_path(&meta)?)
+            } else if meta.path.is_ident("parse_with") {
+                set_once(&meta, &mut result.parse_with, parse_path(&meta)?)
+            } else if meta.path.is_ident("redact") {
+                set_once(&meta, &mut result.redact, Redaction::parse(&meta)?)
+            } else {
+                Err(meta.error(
+                    "unknown field attribute, expected `skip`, `rename`, `with`, `parse_with` or `redact`",
+                ))
+            }
+        })?;
+
+        if result.redact.is_some() && (result.skip || result.with.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`redact` cannot be combined with `skip` or `with`",
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Returns whether printing the field reads its value, i.e. whether it needs a binding in patterns.
+    pub(crate) fn reads_value(&self) -> bool {
+        !self.skip && self.redact != Some(Redaction::Placeholder)
+    }
+
+    /// Returns the printed label of `field`, applying the container's or variant's `rename_all` rule, or `None`
+    /// for a tuple field.
+    pub(crate) fn label(&self, field: &syn::Field, rule: Option<RenameRule>) -> Option<String> {
+        let ident = field.ident.as_ref()?;
+        Some(match (&self.rename, rule) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => with_raw_prefix(ident, |name| rule.apply_to_field(name)),
+            (None, None) => ident.to_string(),
+        })
+    }
+}
+
+/// A case convention for `rename_all`. Only conventions producing identifiers are offered, so that renamed
+/// output still parses.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    const ALL: [(&'static str, RenameRule); 6] = [
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ];
+
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let lit: LitStr = meta.value()?.parse()?;
+        let value = lit.value();
+        RenameRule::ALL
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let names: Vec<_> = RenameRule::ALL
+                    .iter()
+                    .map(|(name, _)| format!("\"{}\"", name))
+                    .collect();
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "unknown `rename_all` rule, expected one of {}",
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+
+    /// Converts a `PascalCase` variant name.
+    fn apply_to_variant(self, name: &str) -> String {
+        match self {
+            RenameRule::Pascal => name.to_string(),
+            RenameRule::Lower => name.to_ascii_lowercase(),
+            RenameRule::Upper => name.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, c) in name.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(name)
+                .to_ascii_uppercase(),
+        }
+    }
+
+    /// Converts a `snake_case` field name.
+    fn apply_to_field(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => name.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in name.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                RenameRule::Camel.apply_to_variant(&RenameRule::Pascal.apply_to_field(name))
+            }
+        }
+    }
+}
+
+impl Redaction {
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        if !meta.input.peek(syn::Token![=]) {
+            return Ok(Redaction::Placeholder);
+        }
+        let lit: LitStr = meta.value()?.parse()?;
+        match lit.value().as_str() {
+            "hash" => Ok(Redaction::Hash),
+            _ => Err(syn::Error::new(
+                lit.span(),
+                "unknown redaction, expected `redact` or `redact = \"hash\"`",
+            )),
+        }
+    }
+}
+
+/// Applies `rename` to the name of `ident`, keeping the `r#` prefix of raw identifiers.
+fn with_raw_prefix(ident: &syn::Ident, rename: impl FnOnce(&str) -> String) -> String {
+    let name = ident.to_string();
+    match name.strip_prefix("r#") {
+        Some(unraw) => format!("r#{}", rename(unraw)),
+        None => rename(&name),
+    }
+}
+
+/// Calls `f` for every item of every `#[spore(...)]` attribute in `attrs`.
+fn for_each_spore_meta(
+    attrs: &[Attribute],
+    mut f: impl FnMut(ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs {
+        if attr.path().is_ident("spore") {
+            attr.parse_nested_meta(&mut f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Stores `value` in `slot`, rejecting a second occurrence of the same attribute.
+fn set_once<T>(meta: &ParseNestedMeta, slot: &mut Option<T>, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        let name = meta
+            .path
+            .get_ident()
+            .map(|i| i.to_string())
+            .unwrap_or_default();
+        return Err(meta.error(format!("duplicate `{}` attribute", name)));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Parses `= "name"`, requiring a name that the spore print parser reads as an identifier.
+fn parse_name(meta: &ParseNestedMeta) -> syn::Result<String> {
+    let lit: LitStr = meta.value()?.parse()?;
+    let name = lit.value();
+    let mut chars = name.strip_prefix("r#").unwrap_or(&name).chars();
+    let is_identifier = chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric());
+    if !is_identifier {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("`{}` is not a valid identifier", name),
+        ));
+    }
+    Ok(name)
+}
+
+/// Parses `= "path::to::function"`.
+fn parse_path(meta: &ParseNestedMeta) -> syn::Result<syn::Path> {
+    let lit: LitStr = meta.value()?.parse()?;
+    lit.parse()
+}
@@ -0,0 +1,106 @@
+// crates/spore-print-derive/src/bound.rs
+
+//! Inference of the `where` clauses of generated implementations.
+
+use crate::attr::{ContainerAttrs, FieldAttrs};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{parse_quote, Data, DeriveInput, Generics, Type, TypePath};
+
+/// Returns the generics of `input` with the bounds its generated implementation needs.
+///
+/// With `#[spore(bound = "...")]` on the container, exactly the given predicates are added. Otherwise
+/// `trait_path` is required of every type parameter used in a field for which `uses_trait` returns true, and
+/// `Default` of every type parameter used in a field for which `needs_default` returns true. Uses of a type
+/// parameter `T` as `T::Assoc` bound `T::Assoc` instead of `T`, and uses inside `PhantomData` are ignored.
+pub(crate) fn with_bounds(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    trait_path: proc_macro2::TokenStream,
+    uses_trait: impl Fn(&FieldAttrs) -> bool,
+    needs_default: impl Fn(&FieldAttrs) -> bool,
+) -> syn::Result<Generics> {
+    let mut generics = input.generics.clone();
+    if let Some(bound) = &container.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+        return Ok(generics);
+    }
+
+    let mut uses = TypeParamUses {
+        params: input.generics.type_params().map(|p| &p.ident).collect(),
+        trait_types: Vec::new(),
+        default_types: Vec::new(),
+        collect_default: false,
+    };
+    let fields: Vec<&syn::Field> = match &input.data {
+        Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(data_enum) => data_enum.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(data_union) => data_union.fields.named.iter().collect(),
+    };
+    for field in fields {
+        let attrs = FieldAttrs::from_field(field)?;
+        if uses_trait(&attrs) {
+            uses.collect_default = false;
+            uses.visit_type(&field.ty);
+        }
+        if needs_default(&attrs) {
+            uses.collect_default = true;
+            uses.visit_type(&field.ty);
+        }
+    }
+
+    let where_clause = generics.make_where_clause();
+    for ty in uses.trait_types {
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: #trait_path });
+    }
+    for ty in uses.default_types {
+        where_clause
+            .predicates
+            .push(parse_quote! { #ty: ::core::default::Default });
+    }
+    Ok(generics)
+}
+
+/// Collects the uses of type parameters in field types, without duplicates and in order of appearance.
+struct TypeParamUses<'a> {
+    params: Vec<&'a syn::Ident>,
+    trait_types: Vec<Type>,
+    default_types: Vec<Type>,
+    collect_default: bool,
+}
+
+impl<'ast> Visit<'ast> for TypeParamUses<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        let segments = &ty.path.segments;
+        if segments.last().is_some_and(|s| s.ident == "PhantomData") {
+            return;
+        }
+
+        let is_param_use = ty.qself.is_none()
+            && ty.path.leading_colon.is_none()
+            && self.params.contains(&&segments[0].ident);
+        if is_param_use {
+            // A use as `T::Assoc` holds a value of the associated type, so that type is bounded rather than `T`.
+            let bounded = Type::Path(ty.clone());
+            let types = if self.collect_default {
+                &mut self.default_types
+            } else {
+                &mut self.trait_types
+            };
+            let key = bounded.to_token_stream().to_string();
+            if !types.iter().any(|t| t.to_token_stream().to_string() == key) {
+                types.push(bounded);
+            }
+            return;
+        }
+        visit::visit_type_path(self, ty);
+    }
+
+    // Types inside macros cannot be inspected.
+    fn visit_macro(&mut self, _: &'ast syn::Macro) {}
+}
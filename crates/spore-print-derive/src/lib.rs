@@ -19,7 +19,7 @@
 //!     field1: 42,
 //!     field2: "hello".to_string(),
 //! };
-//! assert_eq!(instance.spore_print(), "MyStruct { field1: 42, field2: hello }");
+//! assert_eq!(instance.spore_print(), r#"MyStruct { field1: 42, field2: "hello" }"#);
 //! ```
 //!
 //! ```
@@ -40,207 +40,472 @@
 //! assert_eq!(instance2.spore_print(), "MyEnum::Variant2(42)");
 //!
 //! let instance3 = MyEnum::Variant3 { field: "hello".to_string() };
-//! assert_eq!(instance3.spore_print(), "MyEnum::Variant3 { field: hello }"); // Regular comment
+//! assert_eq!(instance3.spore_print(), r#"MyEnum::Variant3 { field: "hello" }"#); // Regular comment
+//! ```
+//!
+//! `SporeParse` reads the output of a derived `SporePrint` back into the type:
+//!
+//! ```
+//! use spore_print::{SporeParse, SporePrint};
+//! use spore_print_derive::{SporeParse, SporePrint};
+//!
+//! #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+//! enum Shape {
+//!     Circle { radius: u32 },
+//!     Square(u32),
+//! }
+//!
+//! let shapes = vec![Shape::Circle { radius: 2 }, Shape::Square(3)];
+//! let text = shapes.spore_print();
+//! assert_eq!(text, "[Shape::Circle { radius: 2 }, Shape::Square(3)]");
+//! assert_eq!(Vec::<Shape>::spore_parse(&text).unwrap(), shapes);
+//! ```
+//!
+//! # Attributes
+//!
+//! Both derives read `#[spore(...)]` attributes:
+//!
+//! - `#[spore(rename = "Name")]` on a container, variant or named field prints it under another name.
+//! - `#[spore(rename_all = "...")]` on a struct renames its fields, on an enum its variants, and on a variant
+//!   its fields. The rules are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"` and
+//!   `"SCREAMING_SNAKE_CASE"`.
+//! - `#[spore(skip)]` on a field leaves it out of the print. `SporeParse` fills it with `Default::default()`.
+//! - `#[spore(with = "path")]` on a field prints it with a `fn(&T, &mut dyn fmt::Write) -> fmt::Result`
+//!   instead of `SporePrint`. Deriving `SporeParse` then requires `#[spore(parse_with = "path")]`, a
+//!   `fn(SporeValue) -> Result<T, SporeParseError>`.
+//! - `#[spore(redact)]` on a field prints `<redacted>` instead of its value, and `#[spore(redact = "hash")]`
+//!   prints `<redacted:xxxxxxxx>` with a short digest of the value, like `spore_print::Redacted`. `SporeParse`
+//!   accepts the marker and fills the field with `Default::default()`.
+//! - `#[spore(opaque)]` on a container prints the marker `<Name>` without reading the value. It is the only
+//!   way to derive `SporePrint` for a union, and `SporeParse` cannot be derived for opaque types.
+//! - `#[spore(bound = "T: SporePrint, ...")]` on a container replaces the inferred `where` predicates.
+//! - `#[spore(crate = "path::to::spore_print")]` on a container makes the generated code reach `spore_print`
+//!   through another path, for crates that use it through a re-export.
+//!
+//! Generic types need no hand-written bounds: `SporePrint` is required of every type parameter used in a
+//! printed field, and `SporeParse` of every type parameter used in a parsed field, or `Default` for skipped
+//! and redacted fields. A use as `T::Assoc` bounds the associated type, and uses inside `PhantomData` are
+//! ignored. Qualified paths such as `<T as Trait>::Assoc` are not inferred and need `bound`.
+//!
+//! The generated code only refers to items through absolute paths, so it also compiles under
+//! `#![no_implicit_prelude]` and next to local items named like those of the prelude.
+//!
+//! Unsupported inputs and invalid attributes are reported as compile errors pointing at the offending code.
+//!
+//! ```
+//! use spore_print::SporePrint;
+//! use spore_print_derive::SporePrint;
+//! use std::fmt;
+//!
+//! fn percent(value: &f64, out: &mut dyn fmt::Write) -> fmt::Result {
+//!     write!(out, "\"{}%\"", value * 100.0)
+//! }
+//!
+//! #[derive(SporePrint)]
+//! #[spore(rename_all = "camelCase")]
+//! struct Download {
+//!     file_name: String,
+//!     #[spore(with = "percent")]
+//!     progress: f64,
+//!     #[spore(skip)]
+//!     buffer: Vec<u8>,
+//! }
+//!
+//! let download = Download {
+//!     file_name: "spore.tar".to_string(),
+//!     progress: 0.5,
+//!     buffer: vec![0; 1024],
+//! };
+//! assert_eq!(
+//!     download.spore_print(),
+//!     r#"Download { fileName: "spore.tar", progress: "50%" }"#
+//! );
 //! ```
 
 extern crate proc_macro;
 
+mod attr;
+mod bound;
+mod parse;
+
+use attr::{ContainerAttrs, FieldAttrs, Redaction, RenameRule, VariantAttrs};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, Data, DeriveInput, Fields, WhereClause};
 
-/// Derives the `SporePrint` trait for a struct or enum.
-#[proc_macro_derive(SporePrint)]
+/// Derives the `SporePrint` trait for a struct or enum, or for a union marked `#[spore(opaque)]`.
+#[proc_macro_derive(SporePrint, attributes(spore))]
 pub fn spore_print_derive(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
 
     // Generate the implementation by delegating to the helper function
-    let expanded = impl_spore_print(&input);
+    let expanded = impl_spore_print(&input).unwrap_or_else(syn::Error::into_compile_error);
 
     // Return the generated code as a TokenStream
     TokenStream::from(expanded)
 }
 
+/// Derives the `SporeParse` trait for a struct or enum, accepting exactly what `#[derive(SporePrint)]` emits.
+#[proc_macro_derive(SporeParse, attributes(spore))]
+pub fn spore_parse_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    TokenStream::from(
+        parse::impl_spore_parse(&input).unwrap_or_else(syn::Error::into_compile_error),
+    )
+}
+
 /// Generates the implementation of the `SporePrint` trait for the given input.
-fn impl_spore_print(input: &DeriveInput) -> proc_macro2::TokenStream {
+fn impl_spore_print(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
-    let generics = &input.generics;
+    let container = ContainerAttrs::from_attrs(&input.attrs)?;
+    let krate = container.krate();
+    let generics = bound::with_bounds(
+        input,
+        &container,
+        quote! { #krate::SporePrint },
+        |attrs| !container.opaque && attrs.reads_value() && attrs.with.is_none(),
+        |_| false,
+    )?;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    if container.opaque {
+        let marker = container.name(name);
+        let text = format!("<{}>", marker);
+        return Ok(quote! {
+            impl #impl_generics #krate::SporePrint for #name #ty_generics #where_clause {
+                fn spore_print_to(&self, out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                    ::core::fmt::Write::write_str(out, #text)
+                }
+
+                fn to_spore_value(&self) -> #krate::SporeValue {
+                    #krate::SporeValue::Marker(::core::convert::From::from(#marker))
+                }
+            }
+        });
+    }
+
     match &input.data {
         Data::Struct(data_struct) => impl_spore_print_for_struct(
             name,
+            &container,
+            &krate,
             &impl_generics,
             &ty_generics,
             where_clause,
             data_struct,
         ),
-        Data::Enum(data_enum) => {
-            impl_spore_print_for_enum(name, &impl_generics, &ty_generics, where_clause, data_enum)
+        Data::Enum(data_enum) => impl_spore_print_for_enum(
+            name,
+            &container,
+            &krate,
+            &impl_generics,
+            &ty_generics,
+            where_clause,
+            data_enum,
+        ),
+        Data::Union(data_union) => Err(syn::Error::new_spanned(
+            data_union.union_token,
+            "`SporePrint` cannot be derived for unions, add `#[spore(opaque)]` to print them as `<Name>`",
+        )),
+    }
+}
+
+/// A field to print.
+struct PrintField {
+    /// The printed name of a named field, `None` for a tuple field.
+    label: Option<String>,
+    /// An expression evaluating to a reference to the field value.
+    accessor: proc_macro2::TokenStream,
+    /// The `#[spore(with = "...")]` function printing the field, if any.
+    with: Option<syn::Path>,
+    /// The `#[spore(redact)]` marker replacing the field value, if any.
+    redact: Option<Redaction>,
+}
+
+impl PrintField {
+    /// Generates a statement writing the field value to `out`.
+    fn write(&self, krate: &syn::Path) -> proc_macro2::TokenStream {
+        let accessor = &self.accessor;
+        match (&self.with, self.redact) {
+            (_, Some(Redaction::Placeholder)) => {
+                quote! { ::core::fmt::Write::write_str(out, "<redacted>")?; }
+            }
+            (_, Some(Redaction::Hash)) => quote! {
+                #krate::SporePrint::spore_print_to(&#krate::Redacted::hashed(#accessor), out)?;
+            },
+            (Some(with), None) => quote! { #with(#accessor, out)?; },
+            (None, None) => quote! { #krate::SporePrint::spore_print_to(#accessor, out)?; },
+        }
+    }
+
+    /// Generates an expression building the `SporeValue` of the field value.
+    fn value(&self, krate: &syn::Path) -> proc_macro2::TokenStream {
+        let accessor = &self.accessor;
+        match (&self.with, self.redact) {
+            (_, Some(Redaction::Placeholder)) => {
+                quote! { #krate::SporeValue::Marker(::core::convert::From::from("redacted")) }
+            }
+            (_, Some(Redaction::Hash)) => quote! {
+                #krate::SporePrint::to_spore_value(&#krate::Redacted::hashed(#accessor))
+            },
+            (Some(with), None) => {
+                quote! { #krate::__private::value_with(|out| #with(#accessor, out)) }
+            }
+            (None, None) => quote! { #krate::SporePrint::to_spore_value(#accessor) },
+        }
+    }
+}
+
+/// Collects the printed fields of `shape`, leaving out skipped ones.
+///
+/// `accessor` returns the expression referring to the field at the given index.
+fn print_fields(
+    shape: &Fields,
+    rule: Option<RenameRule>,
+    accessor: impl Fn(usize, &syn::Field) -> proc_macro2::TokenStream,
+) -> syn::Result<Vec<PrintField>> {
+    let mut fields = Vec::new();
+    for (i, field) in shape.iter().enumerate() {
+        let attrs = FieldAttrs::from_field(field)?;
+        if attrs.skip {
+            continue;
         }
-        _ => unimplemented!("SporePrint can only be derived for structs and enums"),
+        fields.push(PrintField {
+            label: attrs.label(field, rule),
+            accessor: accessor(i, field),
+            with: attrs.with,
+            redact: attrs.redact,
+        });
     }
+    Ok(fields)
 }
 
 /// Generates the `SporePrint` implementation for a struct.
 fn impl_spore_print_for_struct(
     name: &syn::Ident,
+    container: &ContainerAttrs,
+    krate: &syn::Path,
     impl_generics: &syn::ImplGenerics,
     ty_generics: &syn::TypeGenerics,
     where_clause: Option<&WhereClause>,
     data_struct: &syn::DataStruct,
-) -> proc_macro2::TokenStream {
-    let fields_fmt = match &data_struct.fields {
-        Fields::Named(fields_named) => {
-            let field_names: Vec<syn::Ident> = fields_named
-                .named
-                .iter()
-                .map(|f| f.ident.clone().unwrap())
-                .collect();
-            let field_accessors = field_names
-                .iter()
-                .map(|ident| quote! { self.#ident.spore_print() });
-            let field_strings = field_names.iter().map(|ident| ident.to_string());
+) -> syn::Result<proc_macro2::TokenStream> {
+    let type_name = container.name(name);
 
-            quote! {
-                vec![
-                    #(
-                        format!("{}: {}", #field_strings, #field_accessors)
-                    ),*
-                ].join(", ")
+    let fields = print_fields(&data_struct.fields, container.rename_all, |i, f| {
+        match &f.ident {
+            Some(ident) => quote! { &self.#ident },
+            None => {
+                let index = syn::Index::from(i);
+                quote! { &self.#index }
             }
         }
-        Fields::Unnamed(fields_unnamed) => {
-            let field_indices: Vec<syn::Index> = (0..fields_unnamed.unnamed.len())
-                .map(syn::Index::from)
-                .collect();
-            let field_accessors = field_indices
-                .iter()
-                .map(|index| quote! { self.#index.spore_print() });
+    })?;
 
-            quote! {
-                vec![
-                    #(
-                        #field_accessors
-                    ),*
-                ].join(", ")
-            }
-        }
-        Fields::Unit => quote! { String::new() },
-    };
+    let write_body = write_struct_shape(krate, &type_name, &data_struct.fields, &fields);
+    let value_body = struct_shape_value(krate, &type_name, &data_struct.fields, &fields);
 
-    // For unnamed (tuple) structs, format without named fields.
-    let format_string = match &data_struct.fields {
-        Fields::Named(_) => quote! { format!("{} {{ {} }}", stringify!(#name), fields) },
-        Fields::Unnamed(_) => quote! { format!("{}({})", stringify!(#name), fields) },
-        Fields::Unit => quote! { stringify!(#name).to_string() },
-    };
+    Ok(quote! {
+        impl #impl_generics #krate::SporePrint for #name #ty_generics #where_clause {
+            fn spore_print_to(&self, out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                #write_body
+            }
 
-    quote! {
-        impl #impl_generics spore_print::SporePrint for #name #ty_generics #where_clause {
-            fn spore_print(&self) -> String {
-                let fields = #fields_fmt;
-                #format_string
+            fn to_spore_value(&self) -> #krate::SporeValue {
+                #value_body
             }
         }
-    }
+    })
 }
 
 /// Generates the `SporePrint` implementation for an enum.
 fn impl_spore_print_for_enum(
     name: &syn::Ident,
+    container: &ContainerAttrs,
+    krate: &syn::Path,
     impl_generics: &syn::ImplGenerics,
     ty_generics: &syn::TypeGenerics,
     where_clause: Option<&WhereClause>,
     data_enum: &syn::DataEnum,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
+    // An empty enum has no values, so matching on `*self` without arms proves the methods unreachable.
     if data_enum.variants.is_empty() {
-        return quote! {
-            impl #impl_generics spore_print::SporePrint for #name #ty_generics #where_clause {
-                fn spore_print(&self) -> String {
-                    panic!("Cannot print an instance of an empty enum {}", stringify!(#name))
+        return Ok(quote! {
+            impl #impl_generics #krate::SporePrint for #name #ty_generics #where_clause {
+                fn spore_print_to(&self, _out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                    match *self {}
+                }
+
+                fn to_spore_value(&self) -> #krate::SporeValue {
+                    match *self {}
                 }
             }
-        };
+        });
     }
 
-    let variant_matches = data_enum.variants.iter().map(|variant| {
+    let enum_name = container.name(name);
+    let mut write_arms = Vec::new();
+    let mut value_arms = Vec::new();
+
+    for variant in &data_enum.variants {
         let variant_ident = &variant.ident;
-        let variant_name = variant_ident.to_string();
-
-        match &variant.fields {
-            Fields::Named(fields_named) => {
-                let field_idents: Vec<_> = fields_named
-                    .named
-                    .iter()
-                    .map(|f| f.ident.clone().unwrap())
-                    .collect();
-                let field_patterns = quote! { { #(#field_idents),* } };
-                let field_accessors = field_idents
-                    .iter()
-                    .map(|ident| quote! { #ident.spore_print() });
-                let field_strings = field_idents.iter().map(|ident| ident.to_string());
-
-                let fields_fmt = quote! {
-                    vec![
-                        #(
-                            format!("{}: {}", #field_strings, #field_accessors)
-                        ),*
-                    ].join(", ")
+        let variant_attrs = VariantAttrs::from_attrs(&variant.attrs)?;
+        let variant_name = variant_attrs.name(variant_ident, container.rename_all);
+
+        // Fields are bound to generated names so they cannot shadow the `out` writer.
+        let fields = print_fields(&variant.fields, variant_attrs.rename_all, |i, _| {
+            let binding = binding(i);
+            quote! { #binding }
+        })?;
+        let patterns = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let binding = if FieldAttrs::from_field(f)?.reads_value() {
+                    binding(i)
+                } else {
+                    quote! { _ }
                 };
+                Ok(match &f.ident {
+                    Some(ident) => quote! { #ident: #binding },
+                    None => binding,
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
 
-                quote! {
-                    #name::#variant_ident #field_patterns => {
-                        let fields = #fields_fmt;
-                        format!("{}::{} {{ {} }}", stringify!(#name), #variant_name, fields)
-                    }
-                }
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote! { #name::#variant_ident { #(#patterns),* } },
+            Fields::Unnamed(_) => quote! { #name::#variant_ident ( #(#patterns),* ) },
+            Fields::Unit => quote! { #name::#variant_ident },
+        };
+
+        let write_body = write_struct_shape(
+            krate,
+            &format!("{}::{}", enum_name, variant_name),
+            &variant.fields,
+            &fields,
+        );
+        let value_body = struct_shape_value(krate, &variant_name, &variant.fields, &fields);
+
+        write_arms.push(quote! {
+            #pattern => {
+                #write_body
             }
-            Fields::Unnamed(fields_unnamed) => {
-                let field_count = fields_unnamed.unnamed.len();
-                let field_idents: Vec<_> = (0..field_count)
-                    .map(|i| {
-                        syn::Ident::new(&format!("field{}", i), proc_macro2::Span::call_site())
-                    })
-                    .collect();
-                let field_patterns = quote! { ( #(#field_idents),* ) };
-                let field_accessors = field_idents
-                    .iter()
-                    .map(|ident| quote! { #ident.spore_print() });
-
-                let fields_fmt = quote! {
-                    vec![
-                        #(
-                            #field_accessors
-                        ),*
-                    ].join(", ")
-                };
+        });
+        value_arms.push(quote! {
+            #pattern => #krate::SporeValue::Variant {
+                enum_name: ::core::convert::From::from(#enum_name),
+                variant: #krate::__private::Box::new(#value_body),
+            }
+        });
+    }
 
-                quote! {
-                    #name::#variant_ident #field_patterns => {
-                        let fields = #fields_fmt;
-                        format!("{}::{}({})", stringify!(#name), #variant_name, fields)
-                    }
+    Ok(quote! {
+        impl #impl_generics #krate::SporePrint for #name #ty_generics #where_clause {
+            fn spore_print_to(&self, out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                match self {
+                    #(#write_arms),*
                 }
             }
-            Fields::Unit => {
-                quote! {
-                    #name::#variant_ident => {
-                        format!("{}::{}", stringify!(#name), #variant_name)
-                    }
+
+            fn to_spore_value(&self) -> #krate::SporeValue {
+                match self {
+                    #(#value_arms),*
                 }
             }
         }
-    });
+    })
+}
 
-    quote! {
-        impl #impl_generics spore_print::SporePrint for #name #ty_generics #where_clause {
-            fn spore_print(&self) -> String {
-                match self {
-                    #(#variant_matches),*
+/// Returns the generated name binding the field at `index` in enum patterns.
+fn binding(index: usize) -> proc_macro2::TokenStream {
+    let ident = syn::Ident::new(&format!("field{}", index), proc_macro2::Span::call_site());
+    quote! { #ident }
+}
+
+/// Generates statements writing a struct-shaped value named `name` with the given `fields` to `out`.
+fn write_struct_shape(
+    krate: &syn::Path,
+    name: &str,
+    shape: &Fields,
+    fields: &[PrintField],
+) -> proc_macro2::TokenStream {
+    match shape {
+        Fields::Named(_) => write_fields(krate, &format!("{} {{ ", name), fields, " }"),
+        // For unnamed (tuple) structs, format without named fields.
+        Fields::Unnamed(_) => write_fields(krate, &format!("{}(", name), fields, ")"),
+        Fields::Unit => write_fields(krate, name, &[], ""),
+    }
+}
+
+/// Generates an expression building the `SporeValue` of a struct-shaped value named `name`.
+fn struct_shape_value(
+    krate: &syn::Path,
+    name: &str,
+    shape: &Fields,
+    fields: &[PrintField],
+) -> proc_macro2::TokenStream {
+    let values = fields.iter().map(|f| f.value(krate));
+
+    match shape {
+        Fields::Named(_) => {
+            let labels = fields.iter().map(|f| f.label.as_deref().unwrap());
+            quote! {
+                #krate::SporeValue::Struct {
+                    name: ::core::convert::From::from(#name),
+                    fields: ::core::convert::From::from([#((::core::convert::From::from(#labels), #values)),*]),
                 }
             }
         }
+        Fields::Unnamed(_) => quote! {
+            #krate::SporeValue::TupleStruct {
+                name: ::core::convert::From::from(#name),
+                fields: ::core::convert::From::from([#(#values),*]),
+            }
+        },
+        Fields::Unit => {
+            quote! { #krate::SporeValue::UnitStruct(::core::convert::From::from(#name)) }
+        }
+    }
+}
+
+/// Generates statements writing `fields` to `out`, separated by commas and wrapped in `open` and `close`.
+///
+/// Adjacent literal text is merged so that every field costs at most one `write_str` call besides its own print.
+fn write_fields(
+    krate: &syn::Path,
+    open: &str,
+    fields: &[PrintField],
+    close: &str,
+) -> proc_macro2::TokenStream {
+    let mut statements = Vec::new();
+    let mut pending = open.to_string();
+
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            pending.push_str(", ");
+        }
+        if let Some(label) = &field.label {
+            pending.push_str(label);
+            pending.push_str(": ");
+        }
+        if field.redact == Some(Redaction::Placeholder) {
+            pending.push_str("<redacted>");
+            continue;
+        }
+        let write = field.write(krate);
+        statements.push(quote! {
+            ::core::fmt::Write::write_str(out, #pending)?;
+            #write
+        });
+        pending.clear();
+    }
+    pending.push_str(close);
+
+    quote! {
+        #(#statements)*
+        ::core::fmt::Write::write_str(out, #pending)
     }
 }
@@ -0,0 +1,191 @@
+// crates/spore-print-derive/src/parse.rs
+
+//! Generation of `SporeParse` implementations, the inverse of the generated `SporePrint` implementations.
+
+use crate::attr::{ContainerAttrs, FieldAttrs, RenameRule, VariantAttrs};
+use crate::bound;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+/// Generates the implementation of the `SporeParse` trait for the given input.
+pub(crate) fn impl_spore_parse(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let container = ContainerAttrs::from_attrs(&input.attrs)?;
+    let krate = container.krate();
+    let generics = bound::with_bounds(
+        input,
+        &container,
+        quote! { #krate::SporeParse },
+        |attrs| !attrs.skip && attrs.redact.is_none() && attrs.parse_with.is_none(),
+        |attrs| attrs.skip || attrs.redact.is_some(),
+    )?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if container.opaque {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`SporeParse` cannot be derived for `#[spore(opaque)]` types, whose print does not contain their value",
+        ));
+    }
+
+    let body = match &input.data {
+        Data::Struct(data_struct) => {
+            let type_name = container.name(name);
+            let constructor = quote! { #name };
+            let arm = struct_shape_arm(
+                &krate,
+                &type_name,
+                &constructor,
+                &data_struct.fields,
+                container.rename_all,
+            )?;
+            let expected = expected_shape(&type_name, &data_struct.fields);
+
+            quote! {
+                match value {
+                    #arm
+                    other => ::core::result::Result::Err(#krate::SporeParseError::mismatch(#expected, &other)),
+                }
+            }
+        }
+        Data::Enum(data_enum) => {
+            let enum_name = container.name(name);
+            let expected = format!("a variant of `{}`", enum_name);
+            let variant_arms = data_enum
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let variant_attrs = VariantAttrs::from_attrs(&variant.attrs)?;
+                    let constructor = quote! { #name::#variant_ident };
+                    struct_shape_arm(
+                        &krate,
+                        &variant_attrs.name(variant_ident, container.rename_all),
+                        &constructor,
+                        &variant.fields,
+                        variant_attrs.rename_all,
+                    )
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote! {
+                match value {
+                    #krate::SporeValue::Variant { enum_name, variant } if enum_name == #enum_name => {
+                        match *variant {
+                            #(#variant_arms)*
+                            other => ::core::result::Result::Err(#krate::SporeParseError::mismatch(
+                                #expected,
+                                &#krate::SporeValue::Variant { enum_name, variant: #krate::__private::Box::new(other) },
+                            )),
+                        }
+                    }
+                    other => ::core::result::Result::Err(#krate::SporeParseError::mismatch(#expected, &other)),
+                }
+            }
+        }
+        Data::Union(data_union) => {
+            return Err(syn::Error::new_spanned(
+                data_union.union_token,
+                "`SporeParse` cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::SporeParse for #name #ty_generics #where_clause {
+            fn from_spore_value(
+                value: #krate::SporeValue,
+            ) -> ::core::result::Result<Self, #krate::SporeParseError> {
+                #body
+            }
+        }
+    })
+}
+
+/// Describes the struct-shaped value named `name` for mismatch errors.
+fn expected_shape(name: &str, shape: &Fields) -> String {
+    match shape {
+        Fields::Named(_) => format!("struct `{}`", name),
+        Fields::Unnamed(_) => format!("tuple struct `{}`", name),
+        Fields::Unit => format!("unit struct `{}`", name),
+    }
+}
+
+/// Generates a match arm accepting the struct-shaped `SporeValue` named `name` and building it with `constructor`.
+///
+/// Skipped fields are not read, and redacted fields must be redaction markers. Both are set to
+/// `Default::default()`.
+fn struct_shape_arm(
+    krate: &syn::Path,
+    name: &str,
+    constructor: &proc_macro2::TokenStream,
+    shape: &Fields,
+    rule: Option<RenameRule>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut bindings = Vec::new();
+    let mut labels = Vec::new();
+    let mut values = Vec::new();
+
+    for field in shape {
+        let attrs = FieldAttrs::from_field(field)?;
+        if attrs.skip {
+            values.push(quote! { ::core::default::Default::default() });
+            continue;
+        }
+
+        let binding = syn::Ident::new(
+            &format!("field{}", bindings.len()),
+            proc_macro2::Span::call_site(),
+        );
+        // Errors point at the field name, or at the position in the printed tuple for tuple fields.
+        let label = attrs
+            .label(field, rule)
+            .unwrap_or_else(|| bindings.len().to_string());
+        if attrs.redact.is_some() {
+            values.push(quote! {{
+                #krate::__private::expect_redacted(#binding).map_err(|e| e.in_field(#label))?;
+                ::core::default::Default::default()
+            }});
+            bindings.push(binding);
+            labels.push(label);
+            continue;
+        }
+        let parse = match (&attrs.parse_with, &attrs.with) {
+            (Some(parse_with), _) => quote! { #parse_with(#binding) },
+            (None, Some(with)) => {
+                return Err(syn::Error::new_spanned(
+                    with,
+                    "deriving `SporeParse` for a field with `with` requires `parse_with`",
+                ))
+            }
+            (None, None) => quote! { #krate::SporeParse::from_spore_value(#binding) },
+        };
+        values.push(quote! { #parse.map_err(|e| e.in_field(#label))? });
+        bindings.push(binding);
+        labels.push(label);
+    }
+
+    Ok(match shape {
+        Fields::Named(fields_named) => {
+            let field_idents = fields_named.named.iter().map(|f| &f.ident);
+
+            quote! {
+                #krate::SporeValue::Struct { name, fields } if name == #name => {
+                    let [#(#bindings),*] = #krate::__private::take_fields(fields, [#(#labels),*])?;
+                    ::core::result::Result::Ok(#constructor {
+                        #(#field_idents: #values),*
+                    })
+                }
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            #krate::SporeValue::TupleStruct { name, fields } if name == #name => {
+                let [#(#bindings),*] = #krate::__private::take_items(fields)?;
+                ::core::result::Result::Ok(#constructor(#(#values),*))
+            }
+        },
+        Fields::Unit => quote! {
+            #krate::SporeValue::UnitStruct(name) if name == #name => ::core::result::Result::Ok(#constructor),
+        },
+    })
+}
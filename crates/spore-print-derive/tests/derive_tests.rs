@@ -1,6 +1,6 @@
-use spore_print::SporePrint;
+use spore_print::{SporeParse, SporePrint, SporeValue};
 // Import trait from the `spore-print` crate.
-use spore_print_derive::SporePrint;
+use spore_print_derive::{SporeParse, SporePrint};
 // Import the procedural macro from `spore-print-derive` crate.
 
 /// Tests `SporePrint` derivation for unit structs.
@@ -28,7 +28,7 @@ fn test_named_fields_struct() {
     };
     assert_eq!(
         instance.spore_print(),
-        "NamedFieldsStruct { field1: 42, field2: hello }"
+        r#"NamedFieldsStruct { field1: 42, field2: "hello" }"#
     );
 }
 
@@ -39,7 +39,7 @@ fn test_tuple_struct() {
     struct TupleStruct(i32, String);
 
     let instance = TupleStruct(42, "hello".to_string());
-    assert_eq!(instance.spore_print(), "TupleStruct(42, hello)");
+    assert_eq!(instance.spore_print(), r#"TupleStruct(42, "hello")"#);
 }
 
 /// Tests `SporePrint` derivation for a struct with generic fields.
@@ -88,7 +88,7 @@ fn test_enum_with_named_fields() {
     };
     assert_eq!(
         instance.spore_print(),
-        "NamedEnum::Variant2 { field2: hello }"
+        r#"NamedEnum::Variant2 { field2: "hello" }"#
     );
 }
 
@@ -105,7 +105,7 @@ fn test_enum_with_unnamed_fields() {
     assert_eq!(instance.spore_print(), "UnnamedEnum::Variant1(42)");
 
     let instance = UnnamedEnum::Variant2("hello".to_string());
-    assert_eq!(instance.spore_print(), "UnnamedEnum::Variant2(hello)");
+    assert_eq!(instance.spore_print(), r#"UnnamedEnum::Variant2("hello")"#);
 }
 
 /// Tests `SporePrint` derivation for an enum with a mix of unit, named, and unnamed variants.
@@ -128,5 +128,1011 @@ fn test_mixed_enum_variants() {
     );
 
     let instance = MixedEnum::UnnamedVariant("hello".to_string());
-    assert_eq!(instance.spore_print(), "MixedEnum::UnnamedVariant(hello)");
+    assert_eq!(
+        instance.spore_print(),
+        r#"MixedEnum::UnnamedVariant("hello")"#
+    );
+}
+
+/// Tests that derived implementations stream into an existing writer.
+#[test]
+fn test_spore_print_to_writer() {
+    #[derive(SporePrint)]
+    struct Inner(u8);
+
+    #[derive(SporePrint)]
+    enum Message {
+        Batch { out: Vec<Inner>, done: bool },
+    }
+
+    let instance = Message::Batch {
+        out: vec![Inner(1), Inner(2)],
+        done: true,
+    };
+    let mut out = String::from("message = ");
+    instance.spore_print_to(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "message = Message::Batch { out: [Inner(1), Inner(2)], done: true }"
+    );
+}
+
+/// Tests that derived implementations build a `SporeValue` that prints like `spore_print`.
+#[test]
+fn test_to_spore_value() {
+    #[derive(SporePrint)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(SporePrint)]
+    enum Shape {
+        Empty,
+        Circle(Point, u32),
+        Polygon { points: Vec<Point> },
+    }
+
+    let point = |x, y| SporeValue::Struct {
+        name: "Point".to_string(),
+        fields: vec![
+            ("x".to_string(), SporeValue::Int(x)),
+            ("y".to_string(), SporeValue::Int(y)),
+        ],
+    };
+
+    let shape = Shape::Polygon {
+        points: vec![Point { x: 0, y: 1 }, Point { x: 2, y: 3 }],
+    };
+    assert_eq!(
+        shape.to_spore_value(),
+        SporeValue::Variant {
+            enum_name: "Shape".to_string(),
+            variant: Box::new(SporeValue::Struct {
+                name: "Polygon".to_string(),
+                fields: vec![(
+                    "points".to_string(),
+                    SporeValue::List(vec![point(0, 1), point(2, 3)])
+                )],
+            }),
+        }
+    );
+    assert_eq!(shape.to_spore_value().spore_print(), shape.spore_print());
+
+    let shape = Shape::Circle(Point { x: 1, y: 1 }, 5);
+    assert_eq!(
+        shape.to_spore_value(),
+        SporeValue::Variant {
+            enum_name: "Shape".to_string(),
+            variant: Box::new(SporeValue::TupleStruct {
+                name: "Circle".to_string(),
+                fields: vec![point(1, 1), SporeValue::Int(5)],
+            }),
+        }
+    );
+    assert_eq!(shape.to_spore_value().spore_print(), shape.spore_print());
+
+    assert_eq!(
+        Shape::Empty.to_spore_value(),
+        SporeValue::Variant {
+            enum_name: "Shape".to_string(),
+            variant: Box::new(SporeValue::UnitStruct("Empty".to_string())),
+        }
+    );
+}
+
+/// Tests that `SporeParse` reads back the output of derived `SporePrint` implementations.
+#[test]
+fn test_spore_parse_round_trip() {
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Unit;
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Pair(i32, String);
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Fixture {
+        name: String,
+        pairs: Vec<Pair>,
+        unit: Option<Unit>,
+        kind: Kind,
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    enum Kind {
+        Plain,
+        Tagged(char),
+        Scored { score: f64, rank: u8 },
+    }
+
+    for kind in [
+        Kind::Plain,
+        Kind::Tagged('x'),
+        Kind::Scored {
+            score: 0.5,
+            rank: 3,
+        },
+    ] {
+        let fixture = Fixture {
+            name: "fixture \"one\"".to_string(),
+            pairs: vec![Pair(1, "a".to_string()), Pair(-2, "b, c".to_string())],
+            unit: Some(Unit),
+            kind,
+        };
+        let text = fixture.spore_print();
+        assert_eq!(Fixture::spore_parse(&text).unwrap(), fixture, "{}", text);
+    }
+
+    // Fields may appear in any order in hand-written fixtures.
+    assert_eq!(
+        Kind::spore_parse("Kind::Scored { rank: 1, score: 2.5 }").unwrap(),
+        Kind::Scored {
+            score: 2.5,
+            rank: 1
+        }
+    );
+}
+
+/// Tests that `SporeParse` can be derived for fields of standard library types beyond `Vec` and `HashMap`.
+#[test]
+fn test_spore_parse_std_fields() {
+    use std::collections::{BTreeMap, VecDeque};
+    use std::num::NonZeroU32;
+    use std::ops::{Bound, RangeFrom};
+    use std::sync::Arc;
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Index {
+        entries: BTreeMap<String, Vec<u32>>,
+        queue: VecDeque<Box<str>>,
+        shared: Arc<[u8]>,
+        id: NonZeroU32,
+        lower: Bound<u32>,
+        pages: RangeFrom<usize>,
+    }
+
+    let index = Index {
+        entries: BTreeMap::from([("b".to_string(), vec![2]), ("a".to_string(), vec![1, 10])]),
+        queue: VecDeque::from(["q".into()]),
+        shared: Arc::from([1, 2]),
+        id: NonZeroU32::new(7).unwrap(),
+        lower: Bound::Excluded(3),
+        pages: 4..,
+    };
+    let text = index.spore_print();
+    assert_eq!(
+        text,
+        r#"Index { entries: {"a": [1, 10], "b": [2]}, queue: ["q"], shared: [1, 2], id: 7, lower: Bound::Excluded(3), pages: 4.. }"#
+    );
+    assert_eq!(Index::spore_parse(&text).unwrap(), index);
+}
+
+/// Tests that `SporeParse` rejects text that `SporePrint` would not emit for the type.
+#[test]
+fn test_spore_parse_errors() {
+    #[derive(SporeParse, Debug)]
+    #[allow(dead_code)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(SporeParse, Debug)]
+    #[allow(dead_code)]
+    enum Light {
+        Red,
+        Green,
+    }
+
+    let error = Point::spore_parse("Point { x: 1 }").unwrap_err();
+    assert_eq!(error.to_string(), "expected a value, found nothing at `.y`");
+
+    let error = Point::spore_parse("Point { x: 1, y: 2, z: 3 }").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected one of the fields x, y, found `3` at `.z`"
+    );
+
+    let error = Point::spore_parse("Point { x: 1, y: \"2\" }").unwrap_err();
+    assert_eq!(error.to_string(), "expected `i32`, found a string at `.y`");
+
+    let error = Point::spore_parse("Vector { x: 1, y: 2 }").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected struct `Point`, found struct `Vector`"
+    );
+
+    let error = Light::spore_parse("Light::Blue").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a variant of `Light`, found variant `Light::Blue`"
+    );
+
+    let error = Light::spore_parse("Light::Red(1)").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a variant of `Light`, found variant `Light::Red`"
+    );
+
+    assert!(matches!(
+        Light::spore_parse("Light::"),
+        Err(spore_print::SporeParseError::Syntax(_))
+    ));
+}
+
+/// Tests pretty printing of derived types, which is laid out from their `SporeValue`.
+#[test]
+fn test_spore_print_pretty() {
+    #[derive(SporePrint)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(SporePrint)]
+    enum Shape {
+        Polygon { name: String, points: Vec<Point> },
+    }
+
+    let shape = Shape::Polygon {
+        name: "triangle".to_string(),
+        points: vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 0, y: 3 },
+        ],
+    };
+
+    let options = spore_print::PrettyOptions {
+        indent: 2,
+        width: 40,
+    };
+    assert_eq!(
+        shape.spore_print_pretty_with(&options),
+        r#"Shape::Polygon {
+  name: "triangle",
+  points: [
+    Point { x: 0, y: 0 },
+    Point { x: 4, y: 0 },
+    Point { x: 0, y: 3 },
+  ],
+}"#
+    );
+    assert_eq!(
+        spore_print::parse::parse(&shape.spore_print_pretty_with(&options)).unwrap(),
+        shape.to_spore_value()
+    );
+    assert_eq!(
+        Point { x: 1, y: 2 }.spore_print_pretty(),
+        "Point { x: 1, y: 2 }"
+    );
+}
+
+/// Tests that fingerprints of derived types hash their spore print.
+#[test]
+fn test_spore_fingerprint() {
+    #[derive(SporePrint)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(
+        point.spore_fingerprint(),
+        [
+            0x63, 0x4a, 0x87, 0x78, 0x98, 0x1a, 0x91, 0xa6, 0xf7, 0x94, 0x1e, 0x5b, 0xae, 0x53,
+            0x69, 0x6a, 0xcd, 0x5e, 0xee, 0xa4, 0x58, 0x30, 0x26, 0x07, 0xb1, 0x8c, 0xba, 0xb3,
+            0x1a, 0xec, 0x29, 0x52,
+        ]
+    );
+    assert_ne!(
+        point.spore_fingerprint64(),
+        Point { x: 2, y: 1 }.spore_fingerprint64()
+    );
+}
+
+/// Tests `#[spore(skip)]` on named, tuple and variant fields.
+#[test]
+fn test_skip_attribute() {
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Session {
+        user: String,
+        #[spore(skip)]
+        cache: Vec<u8>,
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Pair(#[spore(skip)] u8, u8);
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    enum Event {
+        Login {
+            #[spore(skip)]
+            token: String,
+            user: String,
+        },
+        Tick(#[spore(skip)] u64, u32),
+    }
+
+    let session = Session {
+        user: "ada".to_string(),
+        cache: vec![1, 2, 3],
+    };
+    assert_eq!(session.spore_print(), r#"Session { user: "ada" }"#);
+    assert_eq!(
+        Session::spore_parse(r#"Session { user: "ada" }"#).unwrap(),
+        Session {
+            user: "ada".to_string(),
+            cache: Vec::new(),
+        }
+    );
+
+    assert_eq!(Pair(1, 2).spore_print(), "Pair(2)");
+    assert_eq!(Pair::spore_parse("Pair(2)").unwrap(), Pair(0, 2));
+
+    let login = Event::Login {
+        token: "secret".to_string(),
+        user: "ada".to_string(),
+    };
+    assert_eq!(login.spore_print(), r#"Event::Login { user: "ada" }"#);
+    assert_eq!(
+        login.to_spore_value(),
+        "Event::Login { user: \"ada\" }".parse().unwrap()
+    );
+    assert_eq!(Event::Tick(9, 3).spore_print(), "Event::Tick(3)");
+    assert_eq!(
+        Event::spore_parse("Event::Tick(3)").unwrap(),
+        Event::Tick(0, 3)
+    );
+}
+
+/// Tests `#[spore(rename)]` and `#[spore(rename_all)]` on containers, variants and fields.
+#[test]
+fn test_rename_attributes() {
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    #[spore(rename = "Account", rename_all = "camelCase")]
+    struct UserAccount {
+        user_name: String,
+        #[spore(rename = "id")]
+        account_id: u32,
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    #[spore(rename_all = "snake_case")]
+    enum HttpStatus {
+        NotFound,
+        #[spore(rename_all = "SCREAMING_SNAKE_CASE")]
+        MovedPermanently {
+            new_location: String,
+        },
+        #[spore(rename = "teapot")]
+        ImATeapot(u16),
+    }
+
+    let account = UserAccount {
+        user_name: "ada".to_string(),
+        account_id: 7,
+    };
+    let text = account.spore_print();
+    assert_eq!(text, r#"Account { userName: "ada", id: 7 }"#);
+    assert_eq!(UserAccount::spore_parse(&text).unwrap(), account);
+    assert!(UserAccount::spore_parse(r#"UserAccount { userName: "ada", id: 7 }"#).is_err());
+
+    assert_eq!(HttpStatus::NotFound.spore_print(), "HttpStatus::not_found");
+    let moved = HttpStatus::MovedPermanently {
+        new_location: "/home".to_string(),
+    };
+    let text = moved.spore_print();
+    assert_eq!(
+        text,
+        r#"HttpStatus::moved_permanently { NEW_LOCATION: "/home" }"#
+    );
+    assert_eq!(HttpStatus::spore_parse(&text).unwrap(), moved);
+    assert_eq!(
+        HttpStatus::ImATeapot(418).spore_print(),
+        "HttpStatus::teapot(418)"
+    );
+    assert_eq!(
+        HttpStatus::spore_parse("HttpStatus::teapot(418)").unwrap(),
+        HttpStatus::ImATeapot(418)
+    );
+}
+
+/// Tests `#[spore(with)]` and `#[spore(parse_with)]` for custom per-field formatting.
+#[test]
+fn test_with_attribute() {
+    fn hex(value: &u32, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(out, "\"{:#x}\"", value)
+    }
+
+    fn parse_hex(value: SporeValue) -> Result<u32, spore_print::SporeParseError> {
+        let text = String::from_spore_value(value.clone())?;
+        u32::from_str_radix(text.trim_start_matches("0x"), 16)
+            .map_err(|_| spore_print::SporeParseError::mismatch("a hex string", &value))
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Color {
+        name: String,
+        #[spore(with = "hex", parse_with = "parse_hex")]
+        rgb: u32,
+    }
+
+    #[derive(SporePrint)]
+    enum Paint {
+        Solid(#[spore(with = "hex")] u32),
+    }
+
+    let color = Color {
+        name: "teal".to_string(),
+        rgb: 0x008080,
+    };
+    let text = color.spore_print();
+    assert_eq!(text, r#"Color { name: "teal", rgb: "0x8080" }"#);
+    assert_eq!(
+        color.to_spore_value(),
+        SporeValue::Struct {
+            name: "Color".to_string(),
+            fields: vec![
+                ("name".to_string(), SporeValue::Str("teal".to_string())),
+                ("rgb".to_string(), SporeValue::Str("0x8080".to_string())),
+            ],
+        }
+    );
+    assert_eq!(Color::spore_parse(&text).unwrap(), color);
+    assert_eq!(
+        Color::spore_parse(r#"Color { name: "teal", rgb: "blue" }"#)
+            .unwrap_err()
+            .to_string(),
+        "expected a hex string, found a string at `.rgb`"
+    );
+
+    assert_eq!(Paint::Solid(255).spore_print(), r#"Paint::Solid("0xff")"#);
+}
+
+/// Tests `#[spore(redact)]` and `#[spore(redact = "hash")]` on named, tuple and variant fields.
+#[test]
+fn test_redact_attribute() {
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Login {
+        user: String,
+        #[spore(redact)]
+        password: String,
+        #[spore(redact = "hash")]
+        token: String,
+    }
+
+    #[derive(SporePrint)]
+    #[allow(dead_code)]
+    struct ApiKey(&'static str, #[spore(redact)] &'static str);
+
+    #[derive(SporePrint)]
+    #[allow(dead_code)]
+    enum Credential {
+        Password {
+            user: String,
+            #[spore(redact)]
+            password: String,
+        },
+        Token(#[spore(redact = "hash")] String),
+    }
+
+    let login = Login {
+        user: "ada".to_string(),
+        password: "hunter2".to_string(),
+        token: "token-1234".to_string(),
+    };
+    let text = login.spore_print();
+    assert_eq!(
+        text,
+        r#"Login { user: "ada", password: <redacted>, token: <redacted:e5970fc4> }"#
+    );
+    assert!(!text.contains("hunter2"));
+    assert_eq!(
+        spore_print::parse::parse(&text).unwrap(),
+        login.to_spore_value()
+    );
+    assert_eq!(
+        Login::spore_parse(&text).unwrap(),
+        Login {
+            user: "ada".to_string(),
+            password: String::new(),
+            token: String::new(),
+        }
+    );
+    assert_eq!(
+        Login::spore_parse(r#"Login { user: "ada", password: "hunter2", token: <redacted> }"#)
+            .unwrap_err()
+            .to_string(),
+        "expected `<redacted>`, found a string at `.password`"
+    );
+
+    assert_eq!(
+        ApiKey("service", "sk-123").spore_print(),
+        r#"ApiKey("service", <redacted>)"#
+    );
+
+    let credential = Credential::Password {
+        user: "ada".to_string(),
+        password: "hunter2".to_string(),
+    };
+    assert_eq!(
+        credential.spore_print(),
+        r#"Credential::Password { user: "ada", password: <redacted> }"#
+    );
+    let a = Credential::Token("token-1234".to_string()).spore_print();
+    let b = Credential::Token("token-5678".to_string()).spore_print();
+    assert_eq!(a, "Credential::Token(<redacted:e5970fc4>)");
+    assert_ne!(a, b);
+}
+
+/// Tests `#[spore(opaque)]` on unions and structs, and that empty enums derive without a runtime panic.
+#[test]
+fn test_opaque_and_empty_types() {
+    #[derive(SporePrint)]
+    #[spore(opaque)]
+    #[allow(dead_code)]
+    union Bits {
+        int: u32,
+        float: f32,
+    }
+
+    #[derive(SporePrint)]
+    #[spore(opaque, rename = "Handle")]
+    #[allow(dead_code)]
+    struct FileHandle {
+        fd: i32,
+    }
+
+    #[derive(SporePrint, SporeParse)]
+    enum Never {}
+
+    fn assert_spore_print<T: SporePrint>() {}
+
+    assert_eq!(Bits { int: 1 }.spore_print(), "<Bits>");
+    assert_eq!(
+        (FileHandle { fd: 3 }, 1).to_spore_value(),
+        "(<Handle>, 1)".parse().unwrap()
+    );
+    assert_spore_print::<Never>();
+    assert!(Never::spore_parse("Never::Variant").is_err());
+}
+
+/// Tests that the derives infer bounds for type parameters used in fields, and that `bound` overrides them.
+#[test]
+fn test_inferred_bounds() {
+    trait Backend {
+        type Id;
+    }
+
+    struct Memory;
+
+    impl Backend for Memory {
+        type Id = u32;
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Wrapper<T> {
+        inner: T,
+    }
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    enum Tree<T> {
+        Leaf(T),
+        Node(Vec<Tree<T>>),
+    }
+
+    // `Memory` is neither `SporePrint` nor `Default`: only `B::Id` needs to be.
+    #[derive(SporePrint)]
+    #[allow(dead_code)]
+    struct Record<B: Backend> {
+        id: B::Id,
+        #[spore(skip)]
+        backend: B,
+    }
+
+    // Qualified paths are not inferred, so the bound is given explicitly.
+    #[derive(SporePrint)]
+    #[spore(bound = "<B as Backend>::Id: SporePrint")]
+    struct Key<B: Backend> {
+        id: <B as Backend>::Id,
+    }
+
+    // Skipped fields need `Default` instead of `SporeParse`.
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Cached<T, C> {
+        value: T,
+        #[spore(skip)]
+        cache: Option<C>,
+    }
+
+    let wrapper = Wrapper { inner: vec![1, 2] };
+    assert_eq!(wrapper.spore_print(), "Wrapper { inner: [1, 2] }");
+    assert_eq!(
+        Wrapper::<Vec<i32>>::spore_parse("Wrapper { inner: [1, 2] }").unwrap(),
+        wrapper
+    );
+
+    let tree = Tree::Node(vec![Tree::Leaf('a'), Tree::Node(vec![])]);
+    let text = tree.spore_print();
+    assert_eq!(text, "Tree::Node([Tree::Leaf('a'), Tree::Node([])])");
+    assert_eq!(Tree::spore_parse(&text).unwrap(), tree);
+
+    let record = Record {
+        id: 7,
+        backend: Memory,
+    };
+    assert_eq!(record.spore_print(), "Record { id: 7 }");
+    assert_eq!(Key::<Memory> { id: 8 }.spore_print(), "Key { id: 8 }");
+
+    struct Unprintable;
+
+    let cached = Cached::<_, Unprintable> {
+        value: "a".to_string(),
+        cache: Some(Unprintable),
+    };
+    assert_eq!(cached.spore_print(), r#"Cached { value: "a" }"#);
+    let parsed = Cached::<String, String>::spore_parse(r#"Cached { value: "a" }"#).unwrap();
+    assert_eq!(parsed.cache, None);
+}
+
+/// Types deriving both traits without the prelude, to check that generated code only uses absolute paths.
+#[allow(dead_code)]
+mod no_prelude {
+    #![no_implicit_prelude]
+
+    #[derive(
+        ::spore_print_derive::SporePrint,
+        ::spore_print_derive::SporeParse,
+        ::std::fmt::Debug,
+        ::std::cmp::PartialEq,
+    )]
+    pub struct Account {
+        pub name: ::std::string::String,
+        pub tags: ::std::vec::Vec<u8>,
+        #[spore(redact = "hash")]
+        pub token: ::std::string::String,
+        #[spore(skip)]
+        pub cache: ::std::option::Option<u8>,
+    }
+
+    #[derive(
+        ::spore_print_derive::SporePrint,
+        ::spore_print_derive::SporeParse,
+        ::std::fmt::Debug,
+        ::std::cmp::PartialEq,
+    )]
+    pub enum Event<T> {
+        Created { id: T },
+        Renamed(T, ::std::string::String),
+        Deleted,
+    }
+
+    #[derive(::spore_print_derive::SporePrint)]
+    #[spore(opaque)]
+    pub struct Handle(pub u32);
+}
+
+/// A crate re-exporting `spore_print` under another path.
+mod facade {
+    pub use spore_print;
+}
+
+/// Tests derived code under `#![no_implicit_prelude]` and through a re-exported crate path.
+#[test]
+fn test_hygienic_expansion() {
+    use no_prelude::{Account, Event, Handle};
+
+    let account = Account {
+        name: "ada".to_string(),
+        tags: vec![1, 2],
+        token: "token-1234".to_string(),
+        cache: Some(3),
+    };
+    let text = account.spore_print();
+    assert_eq!(
+        text,
+        r#"Account { name: "ada", tags: [1, 2], token: <redacted:e5970fc4> }"#
+    );
+    let parsed = Account::spore_parse(&text).unwrap();
+    assert_eq!(parsed.name, "ada");
+    assert_eq!(parsed.token, "");
+    assert_eq!(parsed.cache, None);
+
+    let events = vec![
+        Event::Created { id: 1 },
+        Event::Renamed(1, "b".to_string()),
+        Event::Deleted,
+    ];
+    let text = events.spore_print();
+    assert_eq!(
+        text,
+        r#"[Event::Created { id: 1 }, Event::Renamed(1, "b"), Event::Deleted]"#
+    );
+    assert_eq!(Vec::<Event<i32>>::spore_parse(&text).unwrap(), events);
+    assert_eq!(Handle(5).spore_print(), "<Handle>");
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    #[spore(crate = "facade::spore_print")]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(point.spore_print(), "Point { x: 1, y: 2 }");
+    assert_eq!(Point::spore_parse("Point { x: 1, y: 2 }").unwrap(), point);
+}
+
+/// An order with nested items, for the `assert_spore_eq!` tests.
+#[derive(SporePrint, Clone)]
+struct Order {
+    id: u32,
+    items: Vec<Item>,
+}
+
+/// An item of an `Order`.
+#[derive(SporePrint, Clone)]
+struct Item {
+    name: String,
+    quantity: u32,
+}
+
+/// Returns an order with three items.
+fn sample_order() -> Order {
+    let item = |name: &str, quantity| Item {
+        name: name.to_string(),
+        quantity,
+    };
+    Order {
+        id: 7,
+        items: vec![item("bolt", 4), item("nut", 4), item("washer", 8)],
+    }
+}
+
+/// Tests that `assert_spore_eq!` accepts derived values with the same print.
+#[test]
+fn test_assert_spore_eq() {
+    spore_print::assert_spore_eq!(sample_order(), sample_order());
+}
+
+/// Tests that `assert_spore_eq!` reports the path of a differing field of a derived value.
+#[test]
+#[should_panic(
+    expected = "differ at 2 places\n  .items[1].name\n     left: \"nut\"\n    right: \"screw\"\n  \
+                           .items[2].quantity\n     left: 8\n    right: 9"
+)]
+fn test_assert_spore_eq_path() {
+    let mut changed = sample_order();
+    changed.items[1].name = "screw".to_string();
+    changed.items[2].quantity = 9;
+    spore_print::assert_spore_eq!(sample_order(), changed);
+}
+
+/// Tests the snapshots of a derived value, stored in `tests/snapshots`.
+#[test]
+fn test_spore_snapshot() {
+    spore_print::assert_spore_snapshot!(sample_order());
+    spore_print::assert_spore_snapshot!("first_item", sample_order().items[0]);
+}
+
+/// Tests snapshots of derived values holding ids and timestamps that change between runs.
+#[test]
+fn test_spore_snapshot_redactions() {
+    #[derive(SporePrint)]
+    struct Session {
+        id: u64,
+        user: String,
+        created_at: u64,
+        orders: Vec<Order>,
+    }
+
+    let since_epoch = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap();
+    let session = Session {
+        id: since_epoch.as_nanos() as u64,
+        user: "ada".to_string(),
+        created_at: since_epoch.as_secs(),
+        orders: vec![sample_order()],
+    };
+    let redactions = spore_print::Redactions::new()
+        .path(".id", "id")
+        .path(".created_at", "timestamp")
+        .path(".orders[*].id", "order id");
+    spore_print::assert_spore_snapshot!(session, redact = redactions);
+}
+
+/// Tests that reordering the derived items of a list gives a minimal diff.
+#[test]
+fn test_diff_reordered_items() {
+    let old = sample_order();
+    let mut new = sample_order();
+    let bolt = new.items.remove(0);
+    new.items.push(bolt);
+    new.id = 8;
+
+    let changes = spore_print::diff(&old, &new);
+    assert_eq!(
+        changes.to_unified(),
+        "--- left\n+++ right\n\
+         @@ .id @@\n-7\n+8\n\
+         @@ .items[0] @@\n-Item { name: \"bolt\", quantity: 4 }\n\
+         @@ .items[2] @@\n+Item { name: \"bolt\", quantity: 4 }\n"
+    );
+    assert!(spore_print::diff(&old, &sample_order()).is_empty());
+}
+
+/// Tests deriving `SporePrint` for a struct holding the collections of `std::collections`.
+#[test]
+fn test_std_collections_fields() {
+    use std::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+
+    #[derive(SporePrint)]
+    struct Inventory {
+        stock: BTreeMap<String, u32>,
+        tags: BTreeSet<&'static str>,
+        queue: VecDeque<u8>,
+        history: LinkedList<i32>,
+        priorities: BinaryHeap<u16>,
+        codes: Box<[char]>,
+    }
+
+    let inventory = Inventory {
+        stock: BTreeMap::from([("nut".to_string(), 4), ("bolt".to_string(), 2)]),
+        tags: BTreeSet::from(["metal", "hardware"]),
+        queue: VecDeque::from([3, 1]),
+        history: LinkedList::from([-1, 1]),
+        priorities: BinaryHeap::from([2, 9, 5]),
+        codes: Box::new(['x', 'y']),
+    };
+    assert_eq!(
+        inventory.spore_print(),
+        r#"Inventory { stock: {"bolt": 2, "nut": 4}, tags: ["hardware", "metal"], queue: [3, 1], history: [-1, 1], priorities: [2, 5, 9], codes: ['x', 'y'] }"#
+    );
+}
+
+/// Tests deriving `SporePrint` for shared state held behind smart pointers and locks.
+#[test]
+fn test_shared_state_fields() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    #[derive(SporePrint)]
+    struct Counter {
+        hits: Arc<Mutex<u64>>,
+        names: Arc<RwLock<Vec<String>>>,
+        cache: Rc<RefCell<Option<i32>>>,
+        label: Box<str>,
+    }
+
+    let counter = Counter {
+        hits: Arc::new(Mutex::new(3)),
+        names: Arc::new(RwLock::new(vec!["ada".to_string()])),
+        cache: Rc::new(RefCell::new(None)),
+        label: "main".into(),
+    };
+    assert_eq!(
+        counter.spore_print(),
+        r#"Counter { hits: 3, names: ["ada"], cache: None, label: "main" }"#
+    );
+
+    let hits = Arc::clone(&counter.hits);
+    let _guard = hits.lock().unwrap();
+    assert_eq!(
+        counter.spore_print(),
+        r#"Counter { hits: <locked>, names: ["ada"], cache: None, label: "main" }"#
+    );
+}
+
+/// Tests that print options reach the fields of derived types.
+#[test]
+fn test_print_options_in_derived_fields() {
+    use std::num::NonZeroU32;
+    use std::sync::atomic::AtomicU64;
+
+    #[derive(SporePrint)]
+    struct Stats {
+        id: NonZeroU32,
+        total: u128,
+        count: AtomicU64,
+        ratio: Option<f32>,
+    }
+
+    let stats = Stats {
+        id: NonZeroU32::new(1).unwrap(),
+        total: 10,
+        count: AtomicU64::new(2),
+        ratio: Some(0.5),
+    };
+    assert_eq!(
+        stats.spore_print(),
+        "Stats { id: 1, total: 10, count: 2, ratio: Some(0.5) }"
+    );
+    let options = spore_print::PrintOptions {
+        type_suffixes: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        stats.spore_print_with(&options),
+        "Stats { id: 1u32, total: 10u128, count: 2u64, ratio: Some(0.5f32) }"
+    );
+}
+
+/// Tests deriving `SporePrint` for a query builder holding open ranges, bounds and orderings.
+#[test]
+fn test_query_builder_fields() {
+    use std::cmp::{Ordering, Reverse};
+    use std::marker::PhantomData;
+    use std::ops::{Bound, RangeFrom, RangeTo};
+
+    #[derive(SporePrint)]
+    struct Query<T> {
+        ids: RangeFrom<u32>,
+        limit: RangeTo<usize>,
+        after: Bound<String>,
+        order: Ordering,
+        rank: Reverse<i8>,
+        row: PhantomData<T>,
+    }
+
+    let query = Query::<Vec<u8>> {
+        ids: 10..,
+        limit: ..50,
+        after: Bound::Excluded("m".to_string()),
+        order: Ordering::Greater,
+        rank: Reverse(1),
+        row: PhantomData,
+    };
+    assert_eq!(
+        query.spore_print(),
+        r#"Query { ids: 10.., limit: ..50, after: Bound::Excluded("m"), order: Ordering::Greater, rank: Reverse(1), row: PhantomData }"#
+    );
+    assert_eq!(
+        query.to_spore_value(),
+        query.spore_print().parse::<SporeValue>().unwrap()
+    );
+}
+
+/// Tests deriving `SporePrint` and `SporeParse` for a struct holding times.
+#[test]
+fn test_time_fields() {
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+    #[derive(SporePrint, SporeParse, Debug, PartialEq)]
+    struct Job {
+        started_at: SystemTime,
+        timeout: Duration,
+        retries: Vec<Duration>,
+    }
+
+    let job = Job {
+        started_at: UNIX_EPOCH + Duration::new(1_715_000_000, 120_000_000),
+        timeout: Duration::from_secs(30),
+        retries: vec![Duration::from_millis(250), Duration::from_micros(1)],
+    };
+    let text = job.spore_print();
+    assert_eq!(
+        text,
+        "Job { started_at: 2024-05-06T12:53:20.12Z, timeout: 30s, retries: [0.25s, 0.000001s] }"
+    );
+    assert_eq!(Job::spore_parse(&text).unwrap(), job);
+    assert_eq!(job.to_spore_value(), text.parse::<SporeValue>().unwrap());
+
+    #[derive(SporePrint)]
+    struct Timer {
+        started: Instant,
+        elapsed: Duration,
+    }
+
+    let timer = Timer {
+        started: Instant::now(),
+        elapsed: Duration::ZERO,
+    };
+    assert_eq!(
+        timer.spore_print(),
+        "Timer { started: <instant>, elapsed: 0s }"
+    );
 }
@@ -0,0 +1,6 @@
+/// Tests the diagnostics reported by the derives for unsupported inputs and invalid `#[spore(...)]` attributes.
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
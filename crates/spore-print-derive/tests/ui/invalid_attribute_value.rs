@@ -0,0 +1,52 @@
+use spore_print_derive::{SporeParse, SporePrint};
+
+#[derive(SporePrint)]
+#[spore(rename_all = "kebab-case")]
+struct Rule {
+    value: u32,
+}
+
+#[derive(SporePrint)]
+struct Name {
+    #[spore(rename = "my-value")]
+    value: u32,
+}
+
+#[derive(SporePrint)]
+struct Tuple(#[spore(rename = "value")] u32);
+
+#[derive(SporePrint)]
+struct Duplicate {
+    #[spore(rename = "a", rename = "b")]
+    value: u32,
+}
+
+#[derive(SporePrint)]
+struct Redaction {
+    #[spore(redact = "mask")]
+    secret: String,
+}
+
+#[derive(SporePrint)]
+struct RedactedAndSkipped {
+    #[spore(redact, skip)]
+    token: String,
+}
+
+#[derive(SporePrint)]
+#[spore(bound = "T SporePrint")]
+struct Bound<T> {
+    value: T,
+}
+
+fn hex(value: &u32, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+    write!(out, "\"{:x}\"", value)
+}
+
+#[derive(SporeParse)]
+struct WithoutParseWith {
+    #[spore(with = "hex")]
+    value: u32,
+}
+
+fn main() {}
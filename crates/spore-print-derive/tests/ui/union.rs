@@ -0,0 +1,16 @@
+use spore_print_derive::{SporeParse, SporePrint};
+
+#[derive(SporePrint)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+#[derive(SporeParse)]
+#[spore(opaque)]
+union Word {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+fn main() {}
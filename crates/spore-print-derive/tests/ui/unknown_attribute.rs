@@ -0,0 +1,27 @@
+use spore_print_derive::SporePrint;
+
+#[derive(SporePrint)]
+#[spore(rename_fields = "camelCase")]
+struct Container {
+    value: u32,
+}
+
+#[derive(SporePrint)]
+enum Variant {
+    #[spore(skip)]
+    Empty,
+}
+
+#[derive(SporePrint)]
+struct Field {
+    #[spore(hide)]
+    value: u32,
+}
+
+#[derive(SporePrint)]
+struct NotAList {
+    #[spore = "skip"]
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,154 @@
+use crate::diff::diff_values;
+use crate::{SporePrint, SporeValue};
+use std::fmt::{self, Write};
+
+/// Asserts that two values have the same spore print.
+///
+/// The values may have different types, as long as both implement `SporePrint`. On failure, the panic message
+/// lists every place where the values differ, with its path from the root such as `.items[3].name`, instead of
+/// the two complete prints. A custom message can be given after the values, like with [`assert_eq!`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::assert_spore_eq;
+///
+/// assert_spore_eq!(vec![1, 2, 3], (1..4).collect::<Vec<i32>>());
+/// assert_spore_eq!("text", String::from("text"), "strings print the same");
+/// ```
+///
+/// ```should_panic
+/// use spore_print::assert_spore_eq;
+///
+/// // Panics with:
+/// // assertion `left == right` failed
+/// // the spore prints differ at 1 place
+/// //   [1].1
+/// //      left: "b"
+/// //     right: "c"
+/// assert_spore_eq!(vec![(1, "a"), (2, "b")], vec![(1, "a"), (2, "c")]);
+/// ```
+#[macro_export]
+macro_rules! assert_spore_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__private::assert_spore_eq(&$left, &$right, ::std::option::Option::None)
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__private::assert_spore_eq(
+            &$left,
+            &$right,
+            ::std::option::Option::Some(::std::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Panics with the differences between `left` and `right` if their spore prints differ, for `assert_spore_eq!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_spore_eq<L, R>(left: &L, right: &R, message: Option<fmt::Arguments<'_>>)
+where
+    L: SporePrint + ?Sized,
+    R: SporePrint + ?Sized,
+{
+    if left.spore_print() != right.spore_print() {
+        panic!("{}", failure_message(left, right, message));
+    }
+}
+
+/// Formats the panic message of a failed `assert_spore_eq!`.
+fn failure_message<L, R>(left: &L, right: &R, message: Option<fmt::Arguments<'_>>) -> String
+where
+    L: SporePrint + ?Sized,
+    R: SporePrint + ?Sized,
+{
+    let mut out = String::from("assertion `left == right` failed");
+    if let Some(message) = message {
+        write!(out, ": {}", message).unwrap();
+    }
+    out.push('\n');
+    write_differences(
+        &mut out,
+        &left.to_spore_value(),
+        &right.to_spore_value(),
+        ["left", "right"],
+    );
+    out
+}
+
+/// Writes the places where the prints of two values differ, with the values on each side under `labels`.
+pub(crate) fn write_differences(
+    out: &mut String,
+    left: &SporeValue,
+    right: &SporeValue,
+    labels: [&str; 2],
+) {
+    let diff = diff_values(left, right);
+    let changes = diff.changes();
+    let places = if changes.len() == 1 {
+        "place"
+    } else {
+        "places"
+    };
+    write!(
+        out,
+        "the spore prints differ at {} {}",
+        changes.len(),
+        places
+    )
+    .unwrap();
+
+    let width = labels[0].len().max(labels[1].len());
+    for change in changes {
+        let path = match change.path() {
+            "" => "(root)",
+            path => path,
+        };
+        write!(out, "\n  {}", path).unwrap();
+        for (label, value) in labels
+            .into_iter()
+            .zip([change.old_value(), change.new_value()])
+        {
+            let value = value.map_or_else(|| "(missing)".to_string(), SporePrint::spore_print);
+            write!(out, "\n    {:>width$}: {}", label, value, width = width).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the panic message of a failed assertion
+    #[test]
+    fn test_failure_message() {
+        let left = vec![Some((1, "a")), None];
+        let right = vec![Some((1, "b"))];
+        assert_eq!(
+            failure_message(&left, &right, Some(format_args!("after {} steps", 2))),
+            "assertion `left == right` failed: after 2 steps\n\
+             the spore prints differ at 2 places\n  \
+             [0].1\n     left: \"a\"\n    right: \"b\"\n  \
+             [1]\n     left: None\n    right: (missing)"
+        );
+        assert_eq!(
+            failure_message(&1, &"1", None),
+            "assertion `left == right` failed\n\
+             the spore prints differ at 1 place\n  \
+             (root)\n     left: 1\n    right: \"1\""
+        );
+    }
+
+    /// Tests that values of different types with the same print are equal
+    #[test]
+    fn test_assert_spore_eq() {
+        assert_spore_eq!("text", String::from("text"));
+        assert_spore_eq!(Some(vec![1u8]), Some(vec![1i64]), "{} types", "different");
+    }
+
+    /// Tests that differing values panic
+    #[test]
+    #[should_panic(expected = "the spore prints differ at 1 place\n  .1")]
+    fn test_assert_spore_eq_fails() {
+        assert_spore_eq!((1, 2), (1, 3));
+    }
+}
@@ -0,0 +1,682 @@
+use crate::{write_quoted, PrettyOptions, SporePrint, SporeValue};
+use std::fmt::{self, Write};
+
+/// Returns the structural differences between the spore prints of `left` and `right`.
+///
+/// The values are compared through their [`SporeValue`]s, so they may have different types. Values of the same
+/// shape are compared part by part: struct fields by name, map entries by key, tuple and tuple struct items by
+/// position, and list items along a longest common subsequence of equal items, so that moving an item reports
+/// one removal and one addition rather than a change at every position in between. Unequal items left between
+/// two equal ones are compared pairwise. Anything else is compared by its print, and reported as changed as a
+/// whole, so an empty diff means that both values print the same.
+///
+/// Every [`Change`] has a path from the root to the part it concerns, made of `.field` for struct fields, `.0`
+/// for tuple and tuple struct items, `[0]` for list items, `[key]` for map entries and `.start`/`.end` for range
+/// bounds, the empty path standing for the root. The payloads of `Some`, `Ok`, `Err` and enum variants add no
+/// segment, since the variant is the same on both sides. Removed and changed list items are numbered by their
+/// index in `left`, added ones by their index in `right`.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{diff, Change, SporeValue};
+///
+/// let old = vec![("bolt", 4), ("nut", 4), ("washer", 8)];
+/// let new = vec![("nut", 4), ("washer", 9), ("bolt", 4)];
+/// let changes = diff(&old, &new);
+/// assert_eq!(
+///     changes.changes(),
+///     [
+///         Change::Removed {
+///             path: "[0]".to_string(),
+///             value: "(\"bolt\", 4)".parse().unwrap(),
+///         },
+///         Change::Changed {
+///             path: "[2].1".to_string(),
+///             old: SporeValue::Int(8),
+///             new: SporeValue::Int(9),
+///         },
+///         Change::Added {
+///             path: "[2]".to_string(),
+///             value: "(\"bolt\", 4)".parse().unwrap(),
+///         },
+///     ]
+/// );
+/// assert_eq!(
+///     changes.to_unified(),
+///     "--- left\n+++ right\n@@ [0] @@\n-(\"bolt\", 4)\n@@ [2].1 @@\n-8\n+9\n@@ [2] @@\n+(\"bolt\", 4)\n"
+/// );
+/// ```
+pub fn diff<L, R>(left: &L, right: &R) -> SporeDiff
+where
+    L: SporePrint + ?Sized,
+    R: SporePrint + ?Sized,
+{
+    diff_values(&left.to_spore_value(), &right.to_spore_value())
+}
+
+/// Returns the structural differences between two spore values, as described on [`diff`].
+pub(crate) fn diff_values(left: &SporeValue, right: &SporeValue) -> SporeDiff {
+    let mut changes = Vec::new();
+    collect(&mut String::new(), left, right, &mut changes);
+    SporeDiff { changes }
+}
+
+/// The differences between two values, returned by [`diff`].
+///
+/// The changes can be inspected with [`SporeDiff::changes`], or rendered as text with
+/// [`to_unified`](SporeDiff::to_unified), which is also the `Display` format, or
+/// [`to_colored`](SporeDiff::to_colored) for terminals. A `SporeDiff` is itself `SporePrint`, printing the list
+/// of changes, which makes a machine-readable format that [`crate::parse::parse`] reads back:
+///
+/// ```
+/// use spore_print::{diff, SporePrint};
+///
+/// assert_eq!(
+///     diff(&(1, "a"), &(1, "b")).spore_print(),
+///     r#"[Change::Changed { path: ".1", old: "a", new: "b" }]"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SporeDiff {
+    changes: Vec<Change>,
+}
+
+/// A difference between two values, found at `path`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A part present on the right only.
+    Added { path: String, value: SporeValue },
+    /// A part present on the left only.
+    Removed { path: String, value: SporeValue },
+    /// A part that prints differently on each side.
+    Changed {
+        path: String,
+        old: SporeValue,
+        new: SporeValue,
+    },
+}
+
+impl Change {
+    /// Returns the path from the root to the part this change concerns.
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. } => path,
+        }
+    }
+
+    /// Returns the part on the left, `None` when it was added.
+    pub fn old_value(&self) -> Option<&SporeValue> {
+        match self {
+            Change::Added { .. } => None,
+            Change::Removed { value: old, .. } | Change::Changed { old, .. } => Some(old),
+        }
+    }
+
+    /// Returns the part on the right, `None` when it was removed.
+    pub fn new_value(&self) -> Option<&SporeValue> {
+        match self {
+            Change::Removed { .. } => None,
+            Change::Added { value: new, .. } | Change::Changed { new, .. } => Some(new),
+        }
+    }
+}
+
+impl SporeDiff {
+    /// Returns true if both values print the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes, in the order of the printed values.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Renders the changes as text, with one hunk per change.
+    ///
+    /// Each hunk starts with `@@ path @@`, or `@@ (root) @@` for the whole value, followed by the old part
+    /// prefixed with `-` and the new part prefixed with `+`. Parts are pretty printed, with the prefix on every
+    /// line.
+    pub fn to_unified(&self) -> String {
+        self.render(&Palette::default())
+    }
+
+    /// Renders the changes like [`to_unified`](SporeDiff::to_unified), with ANSI colors for terminals: removed
+    /// lines in red, added lines in green and hunk headers in cyan.
+    pub fn to_colored(&self) -> String {
+        self.render(&Palette {
+            header: "\x1b[1m",
+            hunk: "\x1b[36m",
+            removed: "\x1b[31m",
+            added: "\x1b[32m",
+            reset: "\x1b[0m",
+        })
+    }
+
+    fn render(&self, palette: &Palette) -> String {
+        let mut out = String::new();
+        if self.changes.is_empty() {
+            return out;
+        }
+        let Palette {
+            header,
+            hunk,
+            removed,
+            added,
+            reset,
+        } = palette;
+        writeln!(out, "{header}--- left{reset}\n{header}+++ right{reset}").unwrap();
+        for change in &self.changes {
+            let path = match change.path() {
+                "" => "(root)",
+                path => path,
+            };
+            writeln!(out, "{hunk}@@ {path} @@{reset}").unwrap();
+            for (value, sign, color) in [
+                (change.old_value(), '-', removed),
+                (change.new_value(), '+', added),
+            ] {
+                if let Some(value) = value {
+                    let text = value.spore_print_pretty_with(&PrettyOptions::default());
+                    for line in text.lines() {
+                        writeln!(out, "{color}{sign}{line}{reset}").unwrap();
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The escape sequences surrounding each kind of line of a rendered diff, all empty for plain text.
+#[derive(Default)]
+struct Palette {
+    header: &'static str,
+    hunk: &'static str,
+    removed: &'static str,
+    added: &'static str,
+    reset: &'static str,
+}
+
+impl fmt::Display for SporeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_unified())
+    }
+}
+
+impl SporePrint for Change {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let (variant, path) = match self {
+            Change::Added { path, .. } => ("Added", path),
+            Change::Removed { path, .. } => ("Removed", path),
+            Change::Changed { path, .. } => ("Changed", path),
+        };
+        write!(out, "Change::{} {{ path: ", variant)?;
+        write_quoted(out, path, '"')?;
+        match self {
+            Change::Added { value, .. } | Change::Removed { value, .. } => {
+                out.write_str(", value: ")?;
+                value.spore_print_to(out)?;
+            }
+            Change::Changed { old, new, .. } => {
+                out.write_str(", old: ")?;
+                old.spore_print_to(out)?;
+                out.write_str(", new: ")?;
+                new.spore_print_to(out)?;
+            }
+        }
+        out.write_str(" }")
+    }
+}
+
+impl SporePrint for SporeDiff {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.changes.spore_print_to(out)
+    }
+}
+
+/// Appends the changes between `left` and `right`, both found at `path`, to `out`.
+fn collect(path: &mut String, left: &SporeValue, right: &SporeValue, out: &mut Vec<Change>) {
+    match (left, right) {
+        (SporeValue::List(left), SporeValue::List(right)) => collect_list(path, left, right, out),
+        (SporeValue::Tuple(left), SporeValue::Tuple(right)) if left.len() == right.len() => {
+            collect_items(path, left, right, out)
+        }
+        (
+            SporeValue::TupleStruct { name, fields: left },
+            SporeValue::TupleStruct {
+                name: right_name,
+                fields: right,
+            },
+        ) if name == right_name && left.len() == right.len() => {
+            collect_items(path, left, right, out)
+        }
+        (
+            SporeValue::Struct { name, fields: left },
+            SporeValue::Struct {
+                name: right_name,
+                fields: right,
+            },
+        ) if name == right_name => {
+            let left = left
+                .iter()
+                .map(|(field, value)| (format!(".{}", field), value));
+            let right = right
+                .iter()
+                .map(|(field, value)| (format!(".{}", field), value));
+            collect_keyed(path, left.collect(), right.collect(), out)
+        }
+        (SporeValue::Map(left), SporeValue::Map(right)) => {
+            let segment = |key: &SporeValue| format!("[{}]", key.spore_print());
+            let left = left.iter().map(|(key, value)| (segment(key), value));
+            let right = right.iter().map(|(key, value)| (segment(key), value));
+            collect_keyed(path, left.collect(), right.collect(), out)
+        }
+        (SporeValue::Option(Some(left)), SporeValue::Option(Some(right)))
+        | (SporeValue::Result(Ok(left)), SporeValue::Result(Ok(right)))
+        | (SporeValue::Result(Err(left)), SporeValue::Result(Err(right))) => {
+            collect(path, left, right, out)
+        }
+        (
+            SporeValue::Variant { enum_name, variant },
+            SporeValue::Variant {
+                enum_name: right_enum_name,
+                variant: right_variant,
+            },
+        ) if enum_name == right_enum_name
+            && variant_name(variant) == variant_name(right_variant) =>
+        {
+            collect(path, variant, right_variant, out)
+        }
+        (
+            SporeValue::Range {
+                start,
+                end,
+                inclusive,
+            },
+            SporeValue::Range {
+                start: right_start,
+                end: right_end,
+                inclusive: right_inclusive,
+            },
+        ) if inclusive == right_inclusive
+            && start.is_some() == right_start.is_some()
+            && end.is_some() == right_end.is_some() =>
+        {
+            for (segment, left, right) in [(".start", start, right_start), (".end", end, right_end)]
+            {
+                if let (Some(left), Some(right)) = (left, right) {
+                    let len = path.len();
+                    path.push_str(segment);
+                    collect(path, left, right, out);
+                    path.truncate(len);
+                }
+            }
+        }
+        _ => {
+            if left.spore_print() != right.spore_print() {
+                out.push(Change::Changed {
+                    path: path.clone(),
+                    old: left.clone(),
+                    new: right.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Compares tuple or tuple struct items, which have the same count on both sides, by position.
+fn collect_items(
+    path: &mut String,
+    left: &[SporeValue],
+    right: &[SporeValue],
+    out: &mut Vec<Change>,
+) {
+    for (index, (left, right)) in left.iter().zip(right).enumerate() {
+        let len = path.len();
+        write!(path, ".{}", index).unwrap();
+        collect(path, left, right, out);
+        path.truncate(len);
+    }
+}
+
+/// The largest number of cells `collect_list` builds a longest common subsequence table with. Beyond it, the
+/// items that differ are compared by position instead.
+const MAX_LCS_CELLS: usize = 1_000_000;
+
+/// Compares list items along a longest common subsequence of items with the same print.
+///
+/// The common prefix and suffix are skipped first. The unequal items between two items of the subsequence are
+/// compared pairwise, and the surplus on either side is reported as removed or added. When the remaining items
+/// would need a table of more than [`MAX_LCS_CELLS`], they are compared pairwise as a single gap.
+fn collect_list(
+    path: &mut String,
+    left: &[SporeValue],
+    right: &[SporeValue],
+    out: &mut Vec<Change>,
+) {
+    let left_prints: Vec<String> = left.iter().map(SporePrint::spore_print).collect();
+    let right_prints: Vec<String> = right.iter().map(SporePrint::spore_print).collect();
+
+    let prefix = left_prints
+        .iter()
+        .zip(&right_prints)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = left_prints[prefix..]
+        .iter()
+        .rev()
+        .zip(right_prints[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let (left_end, right_end) = (left.len() - suffix, right.len() - suffix);
+    let (rows, columns) = (left_end - prefix, right_end - prefix);
+    if rows.saturating_mul(columns) > MAX_LCS_CELLS {
+        collect_gap(path, left, right, prefix..left_end, prefix..right_end, out);
+        return;
+    }
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `left[prefix + i..left_end]` and
+    // `right[prefix + j..right_end]`.
+    let mut lengths = vec![vec![0usize; columns + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            lengths[i][j] = if left_prints[prefix + i] == right_prints[prefix + j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut gap_i, mut gap_j) = (0, 0);
+    while i < rows || j < columns {
+        if i < rows && j < columns && left_prints[prefix + i] == right_prints[prefix + j] {
+            collect_gap(
+                path,
+                left,
+                right,
+                prefix + gap_i..prefix + i,
+                prefix + gap_j..prefix + j,
+                out,
+            );
+            i += 1;
+            j += 1;
+            (gap_i, gap_j) = (i, j);
+        } else if i < rows && (j == columns || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            // On ties, removals come before additions.
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    collect_gap(
+        path,
+        left,
+        right,
+        prefix + gap_i..prefix + i,
+        prefix + gap_j..prefix + j,
+        out,
+    );
+}
+
+/// Compares the unmatched list items `left[lefts]` and `right[rights]` pairwise, reporting the surplus.
+fn collect_gap(
+    path: &mut String,
+    left: &[SporeValue],
+    right: &[SporeValue],
+    lefts: std::ops::Range<usize>,
+    rights: std::ops::Range<usize>,
+    out: &mut Vec<Change>,
+) {
+    let paired = lefts.len().min(rights.len());
+    for (i, j) in lefts.clone().zip(rights.clone()) {
+        let len = path.len();
+        write!(path, "[{}]", i).unwrap();
+        collect(path, &left[i], &right[j], out);
+        path.truncate(len);
+    }
+    for i in lefts.skip(paired) {
+        out.push(Change::Removed {
+            path: format!("{}[{}]", path, i),
+            value: left[i].clone(),
+        });
+    }
+    for j in rights.skip(paired) {
+        out.push(Change::Added {
+            path: format!("{}[{}]", path, j),
+            value: right[j].clone(),
+        });
+    }
+}
+
+/// Compares values by path segment. The changes follow the order of `left`, then come the segments only found
+/// in `right`.
+fn collect_keyed(
+    path: &mut String,
+    left: Vec<(String, &SporeValue)>,
+    right: Vec<(String, &SporeValue)>,
+    out: &mut Vec<Change>,
+) {
+    for (segment, value) in &left {
+        let len = path.len();
+        path.push_str(segment);
+        match find(&right, segment) {
+            Some(right) => collect(path, value, right, out),
+            None => out.push(Change::Removed {
+                path: path.clone(),
+                value: (*value).clone(),
+            }),
+        }
+        path.truncate(len);
+    }
+    for (segment, value) in &right {
+        if find(&left, segment).is_none() {
+            out.push(Change::Added {
+                path: format!("{}{}", path, segment),
+                value: (*value).clone(),
+            });
+        }
+    }
+}
+
+/// Returns the value of `entries` at `segment`.
+fn find<'a>(entries: &[(String, &'a SporeValue)], segment: &str) -> Option<&'a SporeValue> {
+    entries
+        .iter()
+        .find(|(other, _)| other == segment)
+        .map(|(_, value)| *value)
+}
+
+/// Returns the name of the struct-shaped payload of an enum variant.
+fn variant_name(variant: &SporeValue) -> Option<&str> {
+    match variant {
+        SporeValue::UnitStruct(name)
+        | SporeValue::Struct { name, .. }
+        | SporeValue::TupleStruct { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse;
+
+    /// Returns the changes between two spore prints as `(path, old, new)` prints.
+    fn changes(left: &str, right: &str) -> Vec<(String, Option<String>, Option<String>)> {
+        diff_values(&parse(left).unwrap(), &parse(right).unwrap())
+            .changes()
+            .iter()
+            .map(|change| {
+                (
+                    change.path().to_string(),
+                    change.old_value().map(SporePrint::spore_print),
+                    change.new_value().map(SporePrint::spore_print),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns a change as expected from `changes`.
+    fn at(
+        path: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) -> (String, Option<String>, Option<String>) {
+        (
+            path.to_string(),
+            old.map(str::to_string),
+            new.map(str::to_string),
+        )
+    }
+
+    /// Tests the paths of changes inside nested values
+    #[test]
+    fn test_paths() {
+        assert_eq!(changes("Order { id: 1 }", "Order { id: 1 }"), vec![]);
+        assert_eq!(
+            changes(
+                r#"Order { items: [Item { name: "bolt" }, Item { name: "nut" }] }"#,
+                r#"Order { items: [Item { name: "bolt" }, Item { name: "washer" }] }"#
+            ),
+            vec![at(".items[1].name", Some(r#""nut""#), Some(r#""washer""#))]
+        );
+        assert_eq!(
+            changes(
+                r#"({"a": Some(1), "b": 2}, Point(1, 2), 0..=3)"#,
+                r#"({"a": Some(5), "c": 2}, Point(1, 4), 0..=4)"#
+            ),
+            vec![
+                at(r#".0["a"]"#, Some("1"), Some("5")),
+                at(r#".0["b"]"#, Some("2"), None),
+                at(r#".0["c"]"#, None, Some("2")),
+                at(".1.1", Some("2"), Some("4")),
+                at(".2.end", Some("3"), Some("4")),
+            ]
+        );
+    }
+
+    /// Tests that values of different shapes are reported as a whole
+    #[test]
+    fn test_shape_changes() {
+        assert_eq!(
+            changes("Shape::Circle { radius: 2 }", "Shape::Square(2)"),
+            vec![at(
+                "",
+                Some("Shape::Circle { radius: 2 }"),
+                Some("Shape::Square(2)")
+            )]
+        );
+        assert_eq!(
+            changes("Shape::Circle { radius: 2 }", "Shape::Circle { radius: 3 }"),
+            vec![at(".radius", Some("2"), Some("3"))]
+        );
+        assert_eq!(
+            changes("(1, 2)", "(1, 2, 3)"),
+            vec![at("", Some("(1, 2)"), Some("(1, 2, 3)"))]
+        );
+        assert_eq!(
+            changes("Some(1)", "None"),
+            vec![at("", Some("Some(1)"), Some("None"))]
+        );
+    }
+
+    /// Tests the alignment of list items
+    #[test]
+    fn test_list_alignment() {
+        assert_eq!(
+            changes("[1, 2]", "[1, 2, 3]"),
+            vec![at("[2]", None, Some("3"))]
+        );
+        assert_eq!(
+            changes("[1, 2, 3]", "[2, 3]"),
+            vec![at("[0]", Some("1"), None)]
+        );
+        assert_eq!(
+            changes("[A(1), A(2), A(3), A(4)]", "[A(2), A(3), A(4), A(1)]"),
+            vec![at("[0]", Some("A(1)"), None), at("[3]", None, Some("A(1)"))]
+        );
+        assert_eq!(
+            changes("[1, 2, 3, 4]", "[1, 7, 8, 4, 5]"),
+            vec![
+                at("[1]", Some("2"), Some("7")),
+                at("[2]", Some("3"), Some("8")),
+                at("[4]", None, Some("5")),
+            ]
+        );
+        assert_eq!(
+            changes("[P(1, 0), 5, P(2, 0)]", "[P(1, 1), 5]"),
+            vec![
+                at("[0].1", Some("0"), Some("1")),
+                at("[2]", Some("P(2, 0)"), None)
+            ]
+        );
+    }
+
+    /// Tests that long lists are compared without a quadratic table when they share a prefix and suffix, and
+    /// by position when too much of them differs
+    #[test]
+    fn test_large_lists() {
+        let left: Vec<u32> = (0..100_000).collect();
+        let mut right = left.clone();
+        right[50_000] = 7;
+        right.insert(60_000, 8);
+        let changes = diff(&left, &right);
+        assert_eq!(changes.changes().len(), 2);
+        assert_eq!(changes.changes()[0].path(), "[50000]");
+        assert_eq!(changes.changes()[1].path(), "[60000]");
+        assert!(matches!(changes.changes()[1], Change::Added { .. }));
+
+        let left: Vec<u32> = (0..2_000).collect();
+        let right: Vec<u32> = (1..=2_001).map(|i| i * 10_000).collect();
+        let changes = diff(&left, &right);
+        assert_eq!(changes.changes().len(), 2_001);
+        assert_eq!(changes.changes()[1].path(), "[1]");
+        assert_eq!(
+            changes.changes()[1].new_value(),
+            Some(&SporeValue::Int(20_000))
+        );
+        assert!(matches!(changes.changes()[2_000], Change::Added { .. }));
+    }
+
+    /// Tests the text renderers
+    #[test]
+    fn test_renderers() {
+        let changes = diff(
+            &vec![(1, "a"), (2, "b")],
+            &vec![(1, "c"), (2, "b"), (3, "d")],
+        );
+        assert_eq!(
+            changes.to_unified(),
+            "--- left\n+++ right\n@@ [0].1 @@\n-\"a\"\n+\"c\"\n@@ [2] @@\n+(3, \"d\")\n"
+        );
+        assert_eq!(changes.to_string(), changes.to_unified());
+        assert_eq!(
+            diff(&Some(1), &Some(2)).to_colored(),
+            "\x1b[1m--- left\x1b[0m\n\x1b[1m+++ right\x1b[0m\n\x1b[36m@@ (root) @@\x1b[0m\n\x1b[31m-1\x1b[0m\n\x1b[32m+2\x1b[0m\n"
+        );
+        assert_eq!(diff(&1, &1).to_unified(), "");
+
+        let long = vec!["a long string to break the line"; 3];
+        assert!(diff(&1, &long).to_unified().ends_with(
+            "+[\n+    \"a long string to break the line\",\n+    \"a long string to break the line\",\n+    \"a long string to break the line\",\n+]\n"
+        ));
+    }
+
+    /// Tests the machine-readable print of a diff
+    #[test]
+    fn test_spore_print() {
+        let changes = diff(&vec![1, 2], &vec![2, 3]);
+        let text = changes.spore_print();
+        assert_eq!(
+            text,
+            r#"[Change::Removed { path: "[0]", value: 1 }, Change::Added { path: "[1]", value: 3 }]"#
+        );
+        assert_eq!(parse(&text).unwrap().spore_print(), text);
+    }
+}
@@ -1,56 +1,412 @@
+mod assert;
+mod diff;
+mod fingerprint;
 mod macros;
-use std::collections::{HashMap, HashSet};
-use std::ops::{Range, RangeInclusive};
+mod options;
+pub mod parse;
+mod pretty;
+mod redact;
+mod redactions;
+mod snapshot;
+mod spore_parse;
+mod time;
+mod value;
+
+pub use diff::{diff, Change, SporeDiff};
+pub use options::PrintOptions;
+pub use pretty::PrettyOptions;
+pub use redact::Redacted;
+pub use redactions::Redactions;
+pub use spore_parse::{SporeParse, SporeParseError};
+use std::borrow::Cow;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::{self, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::fmt;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+use std::ops::{
+    Bound, ControlFlow, Deref, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive,
+};
+use std::pin::Pin;
+use std::rc::{self, Rc};
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize,
+};
+use std::sync::{self, Arc, Mutex, OnceLock, RwLock, TryLockError};
+use std::task::Poll;
+pub use value::SporeValue;
+
+/// The version of the spore print format.
+///
+/// The text produced by the built-in implementations and by `#[derive(SporePrint)]`, and therefore every
+/// [`spore_fingerprint`](SporePrint::spore_fingerprint) and [`spore_fingerprint64`](SporePrint::spore_fingerprint64),
+/// stays the same across releases of this crate until this version is bumped. Store it next to persisted
+/// fingerprints to tell a changed value from a changed format.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Items used by code generated in `spore-print-derive`. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::assert::assert_spore_eq;
+    pub use crate::macros::{Joined, Printed};
+    pub use crate::redact::expect_redacted;
+    pub use crate::snapshot::{assert_snapshot, enclosing_function, SnapshotSite};
+    pub use crate::spore_parse::{take_fields, take_items};
+    pub use crate::value::value_with;
+    pub use std::boxed::Box;
+}
 
 /// The `SporePrint` trait provides a method to get a consistent and immutable string representation of a type.
 ///
+/// The representation does not depend on hasher state: `HashMap` entries and `HashSet` elements are printed
+/// sorted by their keys, so equal collections always print identically. Keys are compared by the structure of
+/// their [`SporeValue`], which agrees with `Ord` for the standard types: numbers sort numerically, so
+/// `HashSet::from([10, -2, 1])` prints as `[-2, 1, 10]`, and strings, tuples and derived structs sort field by
+/// field. `BinaryHeap` elements are likewise printed in ascending order rather than in heap order.
+///
+/// `Duration` prints in seconds, as in `1.5s`, and `SystemTime` as an RFC 3339 timestamp in UTC, as in
+/// `2024-05-06T07:08:09.5Z`; both parse back exactly. `Instant` has no meaning outside the run that took it, so
+/// it prints as the marker `<instant>` to keep prints deterministic.
+///
+/// Smart pointers such as `Box`, `Rc` and `Arc` print as the value they point to. Values behind a `RefCell`,
+/// `Mutex` or `RwLock` are read without blocking; when that is not possible a marker is printed instead, such as
+/// `<borrowed>`, `<locked>` or `<poisoned>`. A `Weak` pointer whose value was dropped prints as `<dropped>`, and
+/// an unset `OnceCell` or `OnceLock` as `<uninit>`.
+///
+/// Strings are printed double-quoted and chars single-quoted, with quotes, backslashes and control characters
+/// escaped, so the representation stays unambiguous: `vec!["a, b"]` and `vec!["a", "b"]` never print the same.
+///
+/// Floats are printed in a canonical form that always reads back as a float: finite values use the shortest
+/// digits that round-trip, with a decimal point (`1.0`, `0.25`) or, below `1e-5` and from `1e16` on, an exponent
+/// (`1e-7`, `1.5e300`). Non-finite values print as `NaN`, `inf` and `-inf`; every NaN prints as `NaN` regardless
+/// of its sign and payload. The sign of zero is kept, so `-0.0` prints as `-0.0` and `0.0` as `0.0`.
+///
+/// Implementors must provide at least one of [`spore_print`](SporePrint::spore_print) and
+/// [`spore_print_to`](SporePrint::spore_print_to). All built-in implementations and the derive macro implement
+/// `spore_print_to`, which streams the representation into a writer without allocating a `String` per node.
+///
+/// [`to_spore_value`](SporePrint::to_spore_value) returns the same representation as an owned [`SporeValue`]
+/// tree, which prints back to exactly the text `spore_print` returns.
+///
 /// # Examples
 ///
 /// ```
-/// use spore_print::SporePrint;
+/// use spore_print::{SporePrint, SporeValue};
 ///
 /// let value = 42;
 /// assert_eq!(value.spore_print(), "42");
+///
+/// let mut out = String::new();
+/// vec![Some(1), None].spore_print_to(&mut out).unwrap();
+/// assert_eq!(out, "[Some(1), None]");
+///
+/// let value = (1, "a").to_spore_value();
+/// assert_eq!(
+///     value,
+///     SporeValue::Tuple(vec![SporeValue::Int(1), SporeValue::Str("a".to_string())])
+/// );
+/// assert_eq!(value.spore_print(), r#"(1, "a")"#);
 /// ```
 pub trait SporePrint {
-    fn spore_print(&self) -> String;
+    /// Returns the spore print of `self` as a `String`.
+    fn spore_print(&self) -> String {
+        let mut out = String::new();
+        self.spore_print_to(&mut out)
+            .expect("writing a spore print to a String cannot fail");
+        out
+    }
+
+    /// Writes the spore print of `self` to `out`.
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(&self.spore_print())
+    }
+
+    /// Returns the structural representation of `self`.
+    ///
+    /// The default implementation parses the spore print of `self`, falling back to [`SporeValue::Raw`] when
+    /// the text does not follow the spore print grammar.
+    fn to_spore_value(&self) -> SporeValue {
+        let text = self.spore_print();
+        parse::parse(&text).unwrap_or(SporeValue::Raw(text))
+    }
+
+    /// Returns the spore print of `self` with the given `options`, such as numeric type suffixes.
+    ///
+    /// With the default options this is exactly [`spore_print`](SporePrint::spore_print).
+    fn spore_print_with(&self, options: &PrintOptions) -> String {
+        options.scope(|| self.spore_print())
+    }
+
+    /// Returns the spore print of `self` laid out across lines with the default [`PrettyOptions`].
+    fn spore_print_pretty(&self) -> String {
+        self.spore_print_pretty_with(&PrettyOptions::default())
+    }
+
+    /// Returns the spore print of `self` laid out across lines with the given `options`.
+    ///
+    /// The layout is computed from [`SporePrint::to_spore_value`], so it only depends on the printed value and
+    /// `options`. Values that fit in `options.width` are printed exactly as [`SporePrint::spore_print`].
+    fn spore_print_pretty_with(&self, options: &PrettyOptions) -> String {
+        pretty::to_pretty_string(&self.to_spore_value(), options)
+    }
+
+    /// Returns the SHA-256 digest of the spore print of `self`.
+    ///
+    /// The digest is computed while streaming [`spore_print_to`](SporePrint::spore_print_to), without building
+    /// the text, and equals the SHA-256 of the UTF-8 bytes of [`spore_print`](SporePrint::spore_print). It is
+    /// stable across platforms and crate releases sharing the same [`FORMAT_VERSION`].
+    fn spore_fingerprint(&self) -> [u8; 32] {
+        let mut hasher = fingerprint::Sha256::new();
+        self.spore_print_to(&mut hasher)
+            .expect("writing a spore print to a hasher cannot fail");
+        hasher.finish()
+    }
+
+    /// Returns the 64-bit FNV-1a hash of the spore print of `self`.
+    ///
+    /// A fast, non-cryptographic alternative to [`spore_fingerprint`](SporePrint::spore_fingerprint), with the
+    /// same stability guarantee.
+    fn spore_fingerprint64(&self) -> u64 {
+        let mut hasher = fingerprint::Fnv1a64::new();
+        self.spore_print_to(&mut hasher)
+            .expect("writing a spore print to a hasher cannot fail");
+        hasher.finish()
+    }
 }
 
-// Implement `SporePrint` for types that implement `Display`
-macro_rules! impl_spore_print_for_display {
+// Implement `SporePrint` for `bool` as `true` or `false`
+impl SporePrint for bool {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Bool(*self)
+    }
+}
+
+// Helper function to write the type suffix of a number, such as `u8` in `42u8`, when `PrintOptions::type_suffixes`
+// is in scope
+fn write_type_suffix(out: &mut dyn fmt::Write, suffix: &str) -> fmt::Result {
+    if options::current().type_suffixes {
+        out.write_str(suffix)?;
+    }
+    Ok(())
+}
+
+// Implement `SporePrint` for integers. Every value fits in `SporeValue::Int` except the `u128` values above
+// `i128::MAX`, which become `SporeValue::UInt`.
+macro_rules! impl_spore_print_for_integers {
     ($($t:ty),*) => {
         $(impl SporePrint for $t {
-            fn spore_print(&self) -> String {
-                self.to_string()
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                write!(out, "{}", self)?;
+                write_type_suffix(out, stringify!($t))
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                match i128::try_from(*self) {
+                    Ok(int) => SporeValue::Int(int),
+                    Err(_) => SporeValue::UInt(*self as u128),
+                }
             }
         })*
     };
 }
 
-impl_spore_print_for_display!(
-    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, String, &str, char, bool
-);
+impl_spore_print_for_integers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Implement `SporePrint` for types that print as the number they read with the given method, with its suffix
+macro_rules! impl_spore_print_for_number_wrappers {
+    ($method:ident $args:tt: $($t:ty),*) => {
+        $(impl SporePrint for $t {
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                self.$method $args.spore_print_to(out)
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                self.$method $args.to_spore_value()
+            }
+        })*
+    };
+}
+
+impl_spore_print_for_number_wrappers!(get(): NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128,
+    NonZeroUsize, NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+
+// Atomics are loaded with the ordering of `PrintOptions::atomic_ordering`
+impl_spore_print_for_number_wrappers!(load(options::load_ordering()): AtomicBool, AtomicU8, AtomicU16, AtomicU32,
+    AtomicU64, AtomicUsize, AtomicI8, AtomicI16, AtomicI32, AtomicI64, AtomicIsize);
+
+// Implement `SporePrint` for `Wrapping<T>` and `Saturating<T>` as the value they hold
+macro_rules! impl_spore_print_for_arithmetic_wrappers {
+    ($($t:ident),*) => {
+        $(impl<T> SporePrint for $t<T>
+        where
+            T: SporePrint,
+        {
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                self.0.spore_print_to(out)
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                self.0.to_spore_value()
+            }
+        })*
+    };
+}
+
+impl_spore_print_for_arithmetic_wrappers!(Wrapping, Saturating);
+
+// Implement `SporePrint` for floats in the canonical float format described on `SporePrint`.
+// Each type is formatted from its own shortest round-trip digits, so `0.1f32` prints as `0.1`, and the
+// `SporeValue` is read back from that text, without any type suffix, so that an `f32` widens to the `f64` with
+// the same digits.
+macro_rules! impl_spore_print_for_floats {
+    ($($t:ty),*) => {
+        $(impl SporePrint for $t {
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                if self.is_nan() {
+                    out.write_str("NaN")
+                } else if self.is_infinite() {
+                    out.write_str(if *self > 0.0 { "inf" } else { "-inf" })
+                } else if *self == 0.0 || (1e-5..1e16).contains(&self.abs()) {
+                    let digits = self.to_string();
+                    out.write_str(&digits)?;
+                    if !digits.contains('.') {
+                        out.write_str(".0")?;
+                    }
+                    write_type_suffix(out, stringify!($t))
+                } else {
+                    write!(out, "{:e}", self)?;
+                    write_type_suffix(out, stringify!($t))
+                }
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                let text = self.spore_print();
+                SporeValue::Float(text.trim_end_matches(stringify!($t)).parse().unwrap_or(f64::NAN))
+            }
+        })*
+    };
+}
+
+impl_spore_print_for_floats!(f32, f64);
+
+// Helper function to write quoted text, escaping the quote character, backslashes and control characters so the
+// quoted form can always be told apart from neighbouring punctuation
+pub(crate) fn write_quoted(out: &mut dyn fmt::Write, text: &str, quote: char) -> fmt::Result {
+    out.write_char(quote)?;
+    for c in text.chars() {
+        match c {
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\0' => out.write_str("\\0")?,
+            c if c == quote => {
+                out.write_char('\\')?;
+                out.write_char(c)?;
+            }
+            c if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char(quote)
+}
+
+// Implement `SporePrint` for strings as double-quoted, escaped literals
+impl SporePrint for str {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_quoted(out, self, '"')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Str(self.to_string())
+    }
+}
+
+impl SporePrint for String {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_quoted(out, self, '"')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Str(self.clone())
+    }
+}
+
+// Implement `SporePrint` for `char` as a single-quoted, escaped literal
+impl SporePrint for char {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_quoted(out, self.encode_utf8(&mut [0; 4]), '\'')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Char(*self)
+    }
+}
 
 // Implement `SporePrint` for `Option<T>`
 impl<T> SporePrint for Option<T>
 where
     T: SporePrint,
 {
-    fn spore_print(&self) -> String {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Some(value) => format!("Some({})", value.spore_print()),
-            None => "None".to_string(),
+            Some(value) => {
+                out.write_str("Some(")?;
+                value.spore_print_to(out)?;
+                out.write_char(')')
+            }
+            None => out.write_str("None"),
         }
     }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Option(self.as_ref().map(|value| Box::new(value.to_spore_value())))
+    }
 }
 
 // Implement `SporePrint` for unit type `()`
 impl SporePrint for () {
-    fn spore_print(&self) -> String {
-        "()".to_string()
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("()")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Unit
     }
 }
 
+// Helper function to write items separated by commas between an opening and a closing delimiter
+pub(crate) fn write_items<I>(
+    out: &mut dyn fmt::Write,
+    open: &str,
+    items: I,
+    close: &str,
+) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: SporePrint,
+{
+    out.write_str(open)?;
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            out.write_str(", ")?;
+        }
+        item.spore_print_to(out)?;
+    }
+    out.write_str(close)
+}
+
 // Implement `SporePrint` for collections
 macro_rules! impl_spore_print_for_collections {
     ($($t:ty),*) => {
@@ -59,34 +415,147 @@ macro_rules! impl_spore_print_for_collections {
             where
                 T: SporePrint,
             {
-                fn spore_print(&self) -> String {
-                    let items = self
-                        .iter()
-                        .map(|item| item.spore_print())
-                        .collect::<Vec<_>>();
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    write_items(out, "[", self.iter(), "]")
+                }
 
-                    format!("[{}]", items.join(", "))
+                fn to_spore_value(&self) -> SporeValue {
+                    SporeValue::List(self.iter().map(SporePrint::to_spore_value).collect())
                 }
             }
         )*
     };
 }
 
-impl_spore_print_for_collections!(Vec<T>, HashSet<T>);
+impl_spore_print_for_collections!(Vec<T>, VecDeque<T>, LinkedList<T>, BTreeSet<T>);
+
+// Implement `SporePrint` for `BinaryHeap<T>`.
+// Iteration order depends on the heap layout, so elements are printed in ascending order to keep the output canonical.
+impl<T> SporePrint for BinaryHeap<T>
+where
+    T: SporePrint + Ord,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable();
+        write_items(out, "[", items, "]")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable();
+        SporeValue::List(items.into_iter().map(SporePrint::to_spore_value).collect())
+    }
+}
+
+// Helper function to sort the elements of a hashed collection by their `SporeValue`, which is returned alongside
+fn sorted_by_value<'a, T: SporePrint>(
+    items: impl Iterator<Item = &'a T>,
+) -> Vec<(SporeValue, &'a T)> {
+    let mut items = items
+        .map(|item| (item.to_spore_value(), item))
+        .collect::<Vec<_>>();
+    items.sort_unstable_by(|(a, _), (b, _)| a.canonical_cmp(b));
+    items
+}
 
-// Implement `SporePrint` for `HashMap<K, V>`
-impl<K, V> SporePrint for HashMap<K, V>
+// Implement `SporePrint` for `HashSet<T, S>`.
+// Iteration order depends on the hasher, so elements are sorted by their value to keep the output canonical.
+impl<T, S> SporePrint for HashSet<T, S>
+where
+    T: SporePrint,
+    S: BuildHasher,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let items = sorted_by_value(self.iter());
+        write_items(out, "[", items.into_iter().map(|(_, item)| item), "]")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::List(
+            sorted_by_value(self.iter())
+                .into_iter()
+                .map(|(value, _)| value)
+                .collect(),
+        )
+    }
+}
+
+// Helper function to sort the entries of a hashed map by the `SporeValue` of their key (then of their value),
+// which is returned alongside
+fn sorted_entries<K: SporePrint, V: SporePrint, S>(
+    map: &HashMap<K, V, S>,
+) -> Vec<(SporeValue, &K, &V)> {
+    let mut entries = map
+        .iter()
+        .map(|(key, value)| (key.to_spore_value(), key, value))
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by(|(key_a, _, value_a), (key_b, _, value_b)| {
+        key_a.canonical_cmp(key_b).then_with(|| {
+            value_a
+                .to_spore_value()
+                .canonical_cmp(&value_b.to_spore_value())
+        })
+    });
+    entries
+}
+
+// Implement `SporePrint` for `HashMap<K, V, S>`.
+// Entries are sorted by the value of their key (then by their value) to keep the output canonical.
+impl<K, V, S> SporePrint for HashMap<K, V, S>
 where
     K: SporePrint,
     V: SporePrint,
+    S: BuildHasher,
 {
-    fn spore_print(&self) -> String {
-        let items = self
-            .iter()
-            .map(|(key, value)| format!("{}: {}", key.spore_print(), value.spore_print()))
-            .collect::<Vec<_>>();
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('{')?;
+        for (index, (_, key, value)) in sorted_entries(self).into_iter().enumerate() {
+            if index > 0 {
+                out.write_str(", ")?;
+            }
+            key.spore_print_to(out)?;
+            out.write_str(": ")?;
+            value.spore_print_to(out)?;
+        }
+        out.write_char('}')
+    }
 
-        format!("{{{}}}", items.join(", "))
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Map(
+            sorted_entries(self)
+                .into_iter()
+                .map(|(key, _, value)| (key, value.to_spore_value()))
+                .collect(),
+        )
+    }
+}
+
+// Implement `SporePrint` for `BTreeMap<K, V>`, with entries in the order of their keys
+impl<K, V> SporePrint for BTreeMap<K, V>
+where
+    K: SporePrint,
+    V: SporePrint,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('{')?;
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                out.write_str(", ")?;
+            }
+            key.spore_print_to(out)?;
+            out.write_str(": ")?;
+            value.spore_print_to(out)?;
+        }
+        out.write_char('}')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Map(
+            self.iter()
+                .map(|(key, value)| (key.to_spore_value(), value.to_spore_value()))
+                .collect(),
+        )
     }
 }
 
@@ -97,22 +566,37 @@ macro_rules! impl_spore_print_for_tuples {
     ($T1:ident) => {
         #[allow(non_snake_case)]
         impl<$T1: SporePrint> SporePrint for ($T1,) {
-            fn spore_print(&self) -> String {
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
                 let ($T1,) = self;
-                format!("({},)", $T1.spore_print())
+                out.write_char('(')?;
+                $T1.spore_print_to(out)?;
+                out.write_str(",)")
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                SporeValue::Tuple(vec![self.0.to_spore_value()])
             }
         }
     };
 
     // General case for tuples with more than one element
-    ($($T:ident),+) => {
+    ($T1:ident, $($T:ident),+) => {
         #[allow(non_snake_case)]
-        impl<$($T: SporePrint),+> SporePrint for ($($T,)+) {
-            fn spore_print(&self) -> String {
-                #[allow(non_snake_case)]
-                let ($($T,)+) = self;
-                let values = vec![$($T.spore_print()),+];
-                format!("({})", values.join(", "))
+        impl<$T1: SporePrint, $($T: SporePrint),+> SporePrint for ($T1, $($T,)+) {
+            fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                let ($T1, $($T,)+) = self;
+                out.write_char('(')?;
+                $T1.spore_print_to(out)?;
+                $(
+                    out.write_str(", ")?;
+                    $T.spore_print_to(out)?;
+                )+
+                out.write_char(')')
+            }
+
+            fn to_spore_value(&self) -> SporeValue {
+                let ($T1, $($T,)+) = self;
+                SporeValue::Tuple(vec![$T1.to_spore_value(), $($T.to_spore_value()),+])
             }
         }
     };
@@ -131,22 +615,17 @@ impl_spore_print_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
 impl_spore_print_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
 impl_spore_print_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
 
-// Helper function to format items
-fn format_items<T: SporePrint>(items: &[T]) -> String {
-    let formatted_items = items
-        .iter()
-        .map(|item| item.spore_print())
-        .collect::<Vec<_>>();
-    format!("[{}]", formatted_items.join(", "))
-}
-
 // Implement `SporePrint` for slices
-impl<T> SporePrint for &[T]
+impl<T> SporePrint for [T]
 where
     T: SporePrint,
 {
-    fn spore_print(&self) -> String {
-        format_items(self)
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_items(out, "[", self.iter(), "]")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::List(self.iter().map(SporePrint::to_spore_value).collect())
     }
 }
 
@@ -155,77 +634,419 @@ impl<T, const N: usize> SporePrint for [T; N]
 where
     T: SporePrint,
 {
-    fn spore_print(&self) -> String {
-        format_items(self)
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_items(out, "[", self.iter(), "]")
     }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::List(self.iter().map(SporePrint::to_spore_value).collect())
+    }
+}
+
+// Implement `SporePrint` for references and smart pointers, including `&str`, `&[T]` and `Box<[T]>`, as the value
+// they point to
+macro_rules! impl_spore_print_for_pointers {
+    ($($t:ty),*) => {
+        $(
+            impl<T> SporePrint for $t
+            where
+                T: SporePrint + ?Sized,
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    (**self).spore_print_to(out)
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    (**self).to_spore_value()
+                }
+            }
+        )*
+    };
 }
 
-// Implement `SporePrint` for references
-impl<T> SporePrint for &T
+impl_spore_print_for_pointers!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+// Implement `SporePrint` for `Cow<'_, B>` as the borrowed or owned value
+impl<B> SporePrint for Cow<'_, B>
 where
-    T: SporePrint,
+    B: SporePrint + ToOwned + ?Sized,
 {
-    fn spore_print(&self) -> String {
-        (*self).spore_print()
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).spore_print_to(out)
     }
+
+    fn to_spore_value(&self) -> SporeValue {
+        (**self).to_spore_value()
+    }
+}
+
+// Implement `SporePrint` for `Pin<P>` as the value behind the pinned pointer
+impl<P> SporePrint for Pin<P>
+where
+    P: Deref,
+    P::Target: SporePrint,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).spore_print_to(out)
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        (**self).to_spore_value()
+    }
+}
+
+// Helper function to print a value that may be unavailable, such as the contents of a locked `Mutex`, as the
+// spore print of the value or as a `<marker>` standing in for it
+fn write_available<T: SporePrint + ?Sized>(
+    out: &mut dyn fmt::Write,
+    value: Result<&T, &str>,
+) -> fmt::Result {
+    match value {
+        Ok(value) => value.spore_print_to(out),
+        Err(marker) => write!(out, "<{}>", marker),
+    }
+}
+
+// Helper function to convert a value that may be unavailable into its `SporeValue`, or a `SporeValue::Marker`
+fn available_value<T: SporePrint + ?Sized>(value: Result<&T, &str>) -> SporeValue {
+    match value {
+        Ok(value) => value.to_spore_value(),
+        Err(marker) => SporeValue::Marker(marker.to_string()),
+    }
+}
+
+// Implement `SporePrint` for `Cell<T>` by copying the value out
+impl<T> SporePrint for Cell<T>
+where
+    T: SporePrint + Copy,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.get().spore_print_to(out)
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        self.get().to_spore_value()
+    }
+}
+
+// Implement `SporePrint` for `RefCell<T>`, printing `<borrowed>` while the value is mutably borrowed
+impl<T> SporePrint for RefCell<T>
+where
+    T: SporePrint + ?Sized,
+{
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let value = self.try_borrow();
+        write_available(out, value.as_deref().map_err(|_| "borrowed"))
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        let value = self.try_borrow();
+        available_value(value.as_deref().map_err(|_| "borrowed"))
+    }
+}
+
+// Helper function to name the marker printed when a lock cannot be taken without blocking
+fn lock_marker<G>(error: &TryLockError<G>) -> &'static str {
+    match error {
+        TryLockError::WouldBlock => "locked",
+        TryLockError::Poisoned(_) => "poisoned",
+    }
+}
+
+// Implement `SporePrint` for locks. The lock is only tried, never waited for, so printing cannot deadlock: a lock
+// held elsewhere prints as `<locked>` and a poisoned one as `<poisoned>`.
+macro_rules! impl_spore_print_for_locks {
+    ($($t:ident::$try_lock:ident),*) => {
+        $(
+            impl<T> SporePrint for $t<T>
+            where
+                T: SporePrint + ?Sized,
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    let value = self.$try_lock();
+                    write_available(out, value.as_deref().map_err(lock_marker))
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    let value = self.$try_lock();
+                    available_value(value.as_deref().map_err(lock_marker))
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_print_for_locks!(Mutex::try_lock, RwLock::try_read);
+
+// Implement `SporePrint` for cells initialized at most once, printing `<uninit>` until they are set
+macro_rules! impl_spore_print_for_once_cells {
+    ($($t:ident),*) => {
+        $(
+            impl<T> SporePrint for $t<T>
+            where
+                T: SporePrint,
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    write_available(out, self.get().ok_or("uninit"))
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    available_value(self.get().ok_or("uninit"))
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_print_for_once_cells!(OnceCell, OnceLock);
+
+// Implement `SporePrint` for weak pointers, printing the value if it is still alive and `<dropped>` otherwise
+macro_rules! impl_spore_print_for_weak_pointers {
+    ($($t:ty),*) => {
+        $(
+            impl<T> SporePrint for $t
+            where
+                T: SporePrint + ?Sized,
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    let value = self.upgrade();
+                    write_available(out, value.as_deref().ok_or("dropped"))
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    let value = self.upgrade();
+                    available_value(value.as_deref().ok_or("dropped"))
+                }
+            }
+        )*
+    };
 }
 
+impl_spore_print_for_weak_pointers!(rc::Weak<T>, sync::Weak<T>);
+
 // Implement `SporePrint` for `Result<T, E>`
 impl<T, E> SporePrint for Result<T, E>
 where
     T: SporePrint,
     E: SporePrint,
 {
-    fn spore_print(&self) -> String {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Ok(value) => format!("Ok({})", value.spore_print()),
-            Err(err) => format!("Err({})", err.spore_print()),
+            Ok(value) => {
+                out.write_str("Ok(")?;
+                value.spore_print_to(out)?;
+            }
+            Err(err) => {
+                out.write_str("Err(")?;
+                err.spore_print_to(out)?;
+            }
         }
+        out.write_char(')')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Result(match self {
+            Ok(value) => Ok(Box::new(value.to_spore_value())),
+            Err(err) => Err(Box::new(err.to_spore_value())),
+        })
     }
 }
 
-// Implement `SporePrint` for `Range<T>`
-impl<T> SporePrint for Range<T>
-where
-    T: SporePrint,
-{
-    fn spore_print(&self) -> String {
-        format!("{}..{}", self.start.spore_print(), self.end.spore_print())
+// Helper function to write a range with optional bounds, as in `a..b`, `a..`, `..=b` or `..`
+fn write_range(
+    out: &mut dyn fmt::Write,
+    start: Option<&dyn SporePrint>,
+    end: Option<&dyn SporePrint>,
+    inclusive: bool,
+) -> fmt::Result {
+    if let Some(start) = start {
+        start.spore_print_to(out)?;
+    }
+    out.write_str(if inclusive { "..=" } else { ".." })?;
+    match end {
+        Some(end) => end.spore_print_to(out),
+        None => Ok(()),
+    }
+}
+
+// Helper function to convert a range with optional bounds into a `SporeValue::Range`
+fn range_value(
+    start: Option<&dyn SporePrint>,
+    end: Option<&dyn SporePrint>,
+    inclusive: bool,
+) -> SporeValue {
+    SporeValue::Range {
+        start: start.map(|start| Box::new(start.to_spore_value())),
+        end: end.map(|end| Box::new(end.to_spore_value())),
+        inclusive,
+    }
+}
+
+// Implement `SporePrint` for the range types, giving the bounds they have and whether the end is inclusive
+macro_rules! impl_spore_print_for_ranges {
+    ($($t:ty => |$range:ident| ($start:expr, $end:expr, $inclusive:expr)),*) => {
+        $(
+            impl<T> SporePrint for $t
+            where
+                T: SporePrint,
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    let $range = self;
+                    write_range(out, $start, $end, $inclusive)
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    let $range = self;
+                    range_value($start, $end, $inclusive)
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_print_for_ranges!(
+    Range<T> => |range| (Some(&range.start), Some(&range.end), false),
+    RangeInclusive<T> => |range| (Some(range.start()), Some(range.end()), true),
+    RangeFrom<T> => |range| (Some(&range.start), None, false),
+    RangeTo<T> => |range| (None, Some(&range.end), false),
+    RangeToInclusive<T> => |range| (None, Some(&range.end), true)
+);
+
+// Implement `SporePrint` for `RangeFull` as `..`
+impl SporePrint for RangeFull {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_range(out, None, None, false)
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        range_value(None, None, false)
+    }
+}
+
+// Helper function to write an enum variant as derived enums print it, as in `Enum::Variant` or
+// `Enum::Variant(value)`
+fn write_variant(
+    out: &mut dyn fmt::Write,
+    enum_name: &str,
+    variant: &str,
+    value: Option<&dyn SporePrint>,
+) -> fmt::Result {
+    write!(out, "{}::{}", enum_name, variant)?;
+    match value {
+        Some(value) => write_items(out, "(", [value], ")"),
+        None => Ok(()),
+    }
+}
+
+// Helper function to convert an enum variant into a `SporeValue::Variant`
+fn variant_value(enum_name: &str, variant: &str, value: Option<&dyn SporePrint>) -> SporeValue {
+    let variant = match value {
+        Some(value) => SporeValue::TupleStruct {
+            name: variant.to_string(),
+            fields: vec![value.to_spore_value()],
+        },
+        None => SporeValue::UnitStruct(variant.to_string()),
+    };
+    SporeValue::Variant {
+        enum_name: enum_name.to_string(),
+        variant: Box::new(variant),
+    }
+}
+
+// Implement `SporePrint` for enums of the standard library that are not in the prelude, which print with their
+// enum name like derived enums do, as in `Bound::Included(1)` or `Ordering::Less`
+macro_rules! impl_spore_print_for_std_enums {
+    ($(
+        $name:literal: impl$(<$($param:ident),*>)? for $t:ty {
+            $($pattern:pat => ($variant:literal, $value:expr)),* $(,)?
+        }
+    )*) => {
+        $(
+            impl$(<$($param),*>)? SporePrint for $t
+            where
+                $($($param: SporePrint,)*)?
+            {
+                fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+                    match self {
+                        $($pattern => write_variant(out, $name, $variant, $value),)*
+                    }
+                }
+
+                fn to_spore_value(&self) -> SporeValue {
+                    match self {
+                        $($pattern => variant_value($name, $variant, $value),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_print_for_std_enums! {
+    "Bound": impl<T> for Bound<T> {
+        Bound::Included(value) => ("Included", Some(value)),
+        Bound::Excluded(value) => ("Excluded", Some(value)),
+        Bound::Unbounded => ("Unbounded", None),
+    }
+    "Ordering": impl for cmp::Ordering {
+        cmp::Ordering::Less => ("Less", None),
+        cmp::Ordering::Equal => ("Equal", None),
+        cmp::Ordering::Greater => ("Greater", None),
+    }
+    "ControlFlow": impl<B, C> for ControlFlow<B, C> {
+        ControlFlow::Continue(value) => ("Continue", Some(value)),
+        ControlFlow::Break(value) => ("Break", Some(value)),
+    }
+    "Poll": impl<T> for Poll<T> {
+        Poll::Ready(value) => ("Ready", Some(value)),
+        Poll::Pending => ("Pending", None),
+    }
+}
+
+// Implement `SporePrint` for `PhantomData<T>` as the unit struct `PhantomData`, whatever `T` is
+impl<T: ?Sized> SporePrint for PhantomData<T> {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("PhantomData")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::UnitStruct("PhantomData".to_string())
     }
 }
 
-// Implement `SporePrint` for `RangeInclusive<T>`
-impl<T> SporePrint for RangeInclusive<T>
+// Implement `SporePrint` for `Reverse<T>` as the tuple struct `Reverse(value)`
+impl<T> SporePrint for Reverse<T>
 where
     T: SporePrint,
 {
-    fn spore_print(&self) -> String {
-        format!(
-            "{}..={}",
-            self.start().spore_print(),
-            self.end().spore_print()
-        )
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write_items(out, "Reverse(", [&self.0], ")")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::TupleStruct {
+            name: "Reverse".to_string(),
+            fields: vec![self.0.to_spore_value()],
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::{HashMap, HashSet};
-    use std::iter::FromIterator;
 
     /// Tests `SporePrint` implementation for `String`
     #[test]
     fn test_string() {
         let value = String::from("test");
-        assert_eq!(value.spore_print(), "test");
+        assert_eq!(value.spore_print(), r#""test""#);
     }
 
     /// Tests `SporePrint` implementation for `&str`
     #[test]
     fn test_str() {
         let value: &str = "test";
-        assert_eq!(value.spore_print(), "test");
+        assert_eq!(value.spore_print(), r#""test""#);
     }
 
     /// Tests `SporePrint` implementation for integer types
@@ -235,6 +1056,137 @@ mod tests {
         assert_eq!(value.spore_print(), "42");
     }
 
+    /// Tests `SporePrint` implementation for 128-bit integers
+    #[test]
+    fn test_128_bit_integers() {
+        assert_eq!(
+            i128::MIN.spore_print(),
+            "-170141183460469231731687303715884105728"
+        );
+        assert_eq!(
+            u128::MAX.spore_print(),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(i128::MIN.to_spore_value(), SporeValue::Int(i128::MIN));
+        assert_eq!(7u128.to_spore_value(), SporeValue::Int(7));
+        assert_eq!(u128::MAX.to_spore_value(), SporeValue::UInt(u128::MAX));
+    }
+
+    /// Tests `SporePrint` implementation for the numeric wrappers of `std::num` and for atomics
+    #[test]
+    fn test_numeric_wrappers() {
+        use std::sync::atomic::Ordering;
+
+        assert_eq!(NonZeroU8::new(5).unwrap().spore_print(), "5");
+        assert_eq!(NonZeroI128::new(-5).unwrap().spore_print(), "-5");
+        assert_eq!(Wrapping(250u8).spore_print(), "250");
+        assert_eq!(Saturating(-1i16).to_spore_value(), SporeValue::Int(-1));
+        assert_eq!(AtomicBool::new(true).spore_print(), "true");
+        assert_eq!(AtomicUsize::new(3).to_spore_value(), SporeValue::Int(3));
+
+        let atomic = AtomicI64::new(-9);
+        for ordering in [Ordering::Relaxed, Ordering::Release, Ordering::AcqRel] {
+            let options = PrintOptions {
+                atomic_ordering: ordering,
+                ..PrintOptions::default()
+            };
+            assert_eq!(atomic.spore_print_with(&options), "-9");
+        }
+    }
+
+    /// Tests that type suffixes tell apart numbers of different types, and are only printed when asked for
+    #[test]
+    fn test_type_suffixes() {
+        let options = PrintOptions {
+            type_suffixes: true,
+            ..PrintOptions::default()
+        };
+        let value = (1u8, -1i32, u128::MAX, 0.1f32, 1e16f64, f64::NAN);
+        assert_eq!(
+            value.spore_print_with(&options),
+            "(1u8, -1i32, 340282366920938463463374607431768211455u128, 0.1f32, 1e16f64, NaN)"
+        );
+        assert_eq!(
+            value.spore_print(),
+            "(1, -1, 340282366920938463463374607431768211455, 0.1, 1e16, NaN)"
+        );
+        assert_eq!(
+            (
+                NonZeroU16::new(2).unwrap(),
+                Wrapping(3usize),
+                AtomicU8::new(4)
+            )
+                .spore_print_with(&options),
+            "(2u16, 3usize, 4u8)"
+        );
+        assert_ne!(
+            1u8.spore_print_with(&options),
+            1u16.spore_print_with(&options)
+        );
+
+        let parsed = parse::parse(&value.spore_print_with(&options)).unwrap();
+        assert_eq!(parsed.spore_print(), value.spore_print());
+        assert_eq!(
+            options.scope(|| 0.1f32.to_spore_value()),
+            SporeValue::Float(0.1)
+        );
+        assert_eq!(options.scope(|| (1u8, 2i64).spore_print_pretty()), "(1, 2)");
+    }
+
+    /// Tests that strings and chars are quoted and escaped so their representation is unambiguous
+    #[test]
+    fn test_string_escaping() {
+        assert_eq!(vec!["a, b"].spore_print(), r#"["a, b"]"#);
+        assert_eq!(vec!["a", "b"].spore_print(), r#"["a", "b"]"#);
+        assert_eq!("42".spore_print(), r#""42""#);
+        assert_eq!(
+            "say \"hi\"\n\t'ok' \\ done\0\u{7f}".spore_print(),
+            r#""say \"hi\"\n\t'ok' \\ done\0\u{7f}""#
+        );
+        assert_eq!(
+            "caf\u{e9} \u{1F344}".spore_print(),
+            "\"caf\u{e9} \u{1F344}\""
+        );
+    }
+
+    /// Tests the canonical float format for `f32` and `f64`
+    #[test]
+    fn test_floats() {
+        assert_eq!(1.0f64.spore_print(), "1.0");
+        assert_eq!(0.1f64.spore_print(), "0.1");
+        assert_eq!(0.1f32.spore_print(), "0.1");
+        assert_eq!((-2.5f32).spore_print(), "-2.5");
+        assert_eq!(0.0f64.spore_print(), "0.0");
+        assert_eq!((-0.0f64).spore_print(), "-0.0");
+        assert_eq!(1e15f64.spore_print(), "1000000000000000.0");
+        assert_eq!(1e16f64.spore_print(), "1e16");
+        assert_eq!(1.5e300f64.spore_print(), "1.5e300");
+        assert_eq!(0.00001f64.spore_print(), "0.00001");
+        assert_eq!(1e-7f32.spore_print(), "1e-7");
+        assert_eq!(f64::MIN_POSITIVE.spore_print(), "2.2250738585072014e-308");
+        assert_eq!(f64::NAN.spore_print(), "NaN");
+        assert_eq!((-f32::NAN).spore_print(), "NaN");
+        assert_eq!(f64::INFINITY.spore_print(), "inf");
+        assert_eq!(f32::NEG_INFINITY.spore_print(), "-inf");
+        assert_eq!((1.0f32..=2.0).spore_print(), "1.0..=2.0");
+
+        for value in [1.0, -0.0, 1e16, 1.5e-300, f64::MAX, f64::INFINITY] {
+            let parsed = parse::parse(&value.spore_print()).unwrap();
+            assert!(matches!(parsed, SporeValue::Float(f) if f.to_bits() == value.to_bits()));
+        }
+    }
+
+    /// Tests `SporePrint` implementation for `char`, including escaped characters
+    #[test]
+    fn test_char() {
+        assert_eq!('a'.spore_print(), "'a'");
+        assert_eq!('\''.spore_print(), r"'\''");
+        assert_eq!('"'.spore_print(), r#"'"'"#);
+        assert_eq!('\\'.spore_print(), r"'\\'");
+        assert_eq!('\n'.spore_print(), r"'\n'");
+        assert_eq!('\u{1b}'.spore_print(), r"'\u{1b}'");
+    }
+
     /// Tests `SporePrint` implementation for `Option` with `Some` value
     #[test]
     fn test_option_some() {
@@ -267,7 +1219,7 @@ mod tests {
     #[test]
     fn test_result_err() {
         let result: Result<i32, &str> = Err("error");
-        assert_eq!(result.spore_print(), "Err(error)");
+        assert_eq!(result.spore_print(), r#"Err("error")"#);
     }
 
     /// Tests `SporePrint` implementation for `Range<usize>`
@@ -288,7 +1240,7 @@ mod tests {
     #[test]
     fn test_range_string() {
         let range = "a".to_string().."z".to_string();
-        assert_eq!(range.spore_print(), "a..z");
+        assert_eq!(range.spore_print(), r#""a".."z""#);
     }
 
     /// Tests `SporePrint` implementation for `Range<i32>`
@@ -302,68 +1254,241 @@ mod tests {
     #[test]
     fn test_range_char_inclusive() {
         let range = 'a'..='z';
-        assert_eq!(range.spore_print(), "a..=z");
+        assert_eq!(range.spore_print(), "'a'..='z'");
+    }
+
+    /// Tests `SporePrint` implementation for ranges with an open bound, which parse back to the same value
+    #[test]
+    fn test_open_ranges() {
+        assert_eq!((1..).spore_print(), "1..");
+        assert_eq!((..2.5).spore_print(), "..2.5");
+        assert_eq!((..='z').spore_print(), "..='z'");
+        assert_eq!((..).spore_print(), "..");
+        assert_eq!((Some(1).., ..=-1).spore_print(), "(Some(1).., ..=-1)");
+
+        let values = [
+            (1..).to_spore_value(),
+            (..2).to_spore_value(),
+            (..=3).to_spore_value(),
+            (..).to_spore_value(),
+        ];
+        for value in values {
+            assert_eq!(parse::parse(&value.spore_print()).unwrap(), value);
+        }
+    }
+
+    /// Tests `SporePrint` implementation for the enums of the standard library outside the prelude
+    #[test]
+    fn test_std_enums() {
+        assert_eq!(Bound::Included(1).spore_print(), "Bound::Included(1)");
+        assert_eq!(
+            Bound::Excluded("a").spore_print(),
+            r#"Bound::Excluded("a")"#
+        );
+        assert_eq!(Bound::<i32>::Unbounded.spore_print(), "Bound::Unbounded");
+        assert_eq!(1.cmp(&2).spore_print(), "Ordering::Less");
+        assert_eq!(
+            ControlFlow::<&str, i32>::Continue(1).spore_print(),
+            "ControlFlow::Continue(1)"
+        );
+        assert_eq!(
+            ControlFlow::<&str, ()>::Break("stop").spore_print(),
+            r#"ControlFlow::Break("stop")"#
+        );
+        assert_eq!(Poll::Ready(Some(2)).spore_print(), "Poll::Ready(Some(2))");
+        assert_eq!(Poll::<u8>::Pending.spore_print(), "Poll::Pending");
+
+        let values = [
+            Bound::Included(1).to_spore_value(),
+            Bound::<i32>::Unbounded.to_spore_value(),
+            cmp::Ordering::Greater.to_spore_value(),
+            Poll::Ready((1, 2)).to_spore_value(),
+        ];
+        for value in values {
+            assert_eq!(parse::parse(&value.spore_print()).unwrap(), value);
+        }
+    }
+
+    /// Tests `SporePrint` implementation for `PhantomData<T>` and `Reverse<T>`
+    #[test]
+    fn test_marker_and_reverse() {
+        assert_eq!(PhantomData::<String>.spore_print(), "PhantomData");
+        assert_eq!(
+            PhantomData::<str>.to_spore_value().spore_print(),
+            "PhantomData"
+        );
+        assert_eq!(Reverse(3).spore_print(), "Reverse(3)");
+
+        let value = Reverse(vec![1]).to_spore_value();
+        assert_eq!(parse::parse(&value.spore_print()).unwrap(), value);
     }
 
     /// Tests `SporePrint` implementation for vector of strings
     #[test]
     fn test_vec_of_strings() {
         let vec = vec!["one".to_string(), "two".to_string(), "three".to_string()];
-        assert_eq!(vec.spore_print(), "[one, two, three]");
+        assert_eq!(vec.spore_print(), r#"["one", "two", "three"]"#);
     }
 
     /// Tests `SporePrint` implementation for `HashSet<i32>`
     #[test]
     fn test_hashset() {
-        let set: HashSet<i32> = HashSet::from([1, 2]);
-        let expected: HashSet<String> = HashSet::from_iter(vec!["1".to_string(), "2".to_string()]);
-        let actual: HashSet<String> = HashSet::from_iter(
-            set.spore_print()
-                .trim_matches(|c| c == '[' || c == ']')
-                .split(", ")
-                .map(|s| s.to_string()),
+        let set: HashSet<i32> = HashSet::from([2, 1]);
+        assert_eq!(set.spore_print(), "[1, 2]");
+    }
+
+    /// Tests that hashed collections are sorted by value rather than by their printed text
+    #[test]
+    fn test_hashed_collections_sort_by_value() {
+        assert_eq!(HashSet::from([2, 10, 1]).spore_print(), "[1, 2, 10]");
+        assert_eq!(
+            HashSet::from([-1, -2, -10, 0]).spore_print(),
+            "[-10, -2, -1, 0]"
+        );
+        assert_eq!(
+            HashSet::from([u128::MAX, 0, 1 << 100]).spore_print(),
+            "[0, 1267650600228229401496703205376, 340282366920938463463374607431768211455]"
+        );
+        assert_eq!(
+            HashSet::from([(2, "b"), (10, "a"), (2, "a")]).spore_print(),
+            r#"[(2, "a"), (2, "b"), (10, "a")]"#
+        );
+        assert_eq!(
+            HashSet::from([Some(3), None, Some(-3)]).spore_print(),
+            "[None, Some(-3), Some(3)]"
+        );
+        assert_eq!(
+            HashSet::from([vec![1, 2], vec![1], vec![10]]).spore_print(),
+            "[[1], [1, 2], [10]]"
+        );
+
+        let map = HashMap::from([(10, "ten"), (-1, "minus one"), (9, "nine")]);
+        assert_eq!(
+            map.spore_print(),
+            r#"{-1: "minus one", 9: "nine", 10: "ten"}"#
+        );
+        assert_eq!(map.to_spore_value().spore_print(), map.spore_print());
+    }
+
+    /// Tests that the structural order of values is total across numbers of different kinds
+    #[test]
+    fn test_canonical_cmp() {
+        let mut values = vec![
+            SporeValue::Int(1 << 53),
+            SporeValue::Float(f64::INFINITY),
+            SporeValue::UInt(u128::MAX),
+            SporeValue::Float(9007199254740992.0),
+            SporeValue::Int((1 << 53) + 1),
+            SporeValue::Float(-0.5),
+            SporeValue::Int(-1),
+            SporeValue::Str("a".to_string()),
+            SporeValue::Unit,
+        ];
+        values.sort_by(SporeValue::canonical_cmp);
+        assert_eq!(
+            SporeValue::List(values).spore_print(),
+            r#"[(), -1, -0.5, 9007199254740992.0, 9007199254740992, 9007199254740993, 340282366920938463463374607431768211455, inf, "a"]"#
         );
-        assert_eq!(actual, expected);
     }
 
     /// Tests `SporePrint` implementation for `HashMap<&str, i32>`
     #[test]
     fn test_hashmap() {
-        let map: HashMap<&str, i32> = HashMap::from([("key1", 1), ("key2", 2)]);
-        let expected: HashSet<String> =
-            HashSet::from_iter(vec!["key1: 1".to_string(), "key2: 2".to_string()]);
-        let actual: HashSet<String> = HashSet::from_iter(
-            map.spore_print()
-                .trim_matches(|c| c == '{' || c == '}')
-                .split(", ")
-                .map(|s| s.to_string()),
+        let map: HashMap<&str, i32> = HashMap::from([("key2", 2), ("key1", 1)]);
+        assert_eq!(map.spore_print(), r#"{"key1": 1, "key2": 2}"#);
+    }
+
+    /// Tests `SporePrint` implementation for hashed collections with a custom hasher
+    #[test]
+    fn test_hashed_collections_with_custom_hasher() {
+        use std::hash::BuildHasherDefault;
+        type Hasher = BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+
+        let set: HashSet<i32, Hasher> = [3, 1, 2].into_iter().collect();
+        assert_eq!(set.spore_print(), "[1, 2, 3]");
+        let map: HashMap<&str, i32, Hasher> = [("b", 2), ("a", 1)].into_iter().collect();
+        assert_eq!(map.spore_print(), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(map.to_spore_value().spore_print(), map.spore_print());
+    }
+
+    /// Tests `SporePrint` implementation for the ordered and sequential collections of `std::collections`
+    #[test]
+    fn test_std_collections() {
+        let map = BTreeMap::from([("b", vec![2]), ("a", vec![1])]);
+        assert_eq!(map.spore_print(), r#"{"a": [1], "b": [2]}"#);
+        assert_eq!(BTreeSet::from([3, 1, 2]).spore_print(), "[1, 2, 3]");
+        assert_eq!(VecDeque::from([1, 2, 3]).spore_print(), "[1, 2, 3]");
+        assert_eq!(LinkedList::from(['a', 'b']).spore_print(), "['a', 'b']");
+        assert_eq!(BTreeMap::<i32, i32>::new().spore_print(), "{}");
+
+        for value in [
+            map.to_spore_value(),
+            BTreeSet::from([2, 1]).to_spore_value(),
+        ] {
+            assert_eq!(parse::parse(&value.spore_print()).unwrap(), value);
+        }
+    }
+
+    /// Tests that a `BinaryHeap` prints its elements in ascending order regardless of insertion order
+    #[test]
+    fn test_binary_heap() {
+        let forward = BinaryHeap::from(vec![1, 5, 2, 4, 3]);
+        let backward = BinaryHeap::from(vec![3, 4, 2, 5, 1]);
+        assert_eq!(forward.spore_print(), "[1, 2, 3, 4, 5]");
+        assert_eq!(backward.spore_print(), forward.spore_print());
+        assert_eq!(forward.to_spore_value().spore_print(), "[1, 2, 3, 4, 5]");
+    }
+
+    /// Tests that hashed collections print identically regardless of insertion order and hasher seed
+    #[test]
+    fn test_hashed_collections_are_canonical() {
+        let keys = ["delta", "alpha", "charlie", "bravo", "echo", "foxtrot"];
+
+        let forward: HashMap<&str, usize> = keys.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+        let backward: HashMap<&str, usize> = keys
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, k)| (*k, i))
+            .collect();
+        assert_eq!(forward.spore_print(), backward.spore_print());
+        assert_eq!(
+            forward.spore_print(),
+            r#"{"alpha": 1, "bravo": 3, "charlie": 2, "delta": 0, "echo": 4, "foxtrot": 5}"#
+        );
+
+        let forward: HashSet<&str> = keys.iter().copied().collect();
+        let backward: HashSet<&str> = keys.iter().copied().rev().collect();
+        assert_eq!(forward.spore_print(), backward.spore_print());
+        assert_eq!(
+            forward.spore_print(),
+            r#"["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"]"#
         );
-        assert_eq!(actual, expected);
     }
 
     /// Tests `SporePrint` implementation for tuples of varying lengths
     #[test]
     fn test_tuples() {
         let tuple_2 = (42, "hello");
-        assert_eq!(tuple_2.spore_print(), "(42, hello)");
+        assert_eq!(tuple_2.spore_print(), r#"(42, "hello")"#);
 
         let tuple_3 = (42, "hello", Some(std::f64::consts::PI));
         assert_eq!(
             tuple_3.spore_print(),
-            "(42, hello, Some(3.141592653589793))"
+            r#"(42, "hello", Some(3.141592653589793))"#
         );
 
         let tuple_4 = (42, "hello", std::f64::consts::PI, true);
         assert_eq!(
             tuple_4.spore_print(),
-            "(42, hello, 3.141592653589793, true)"
+            r#"(42, "hello", 3.141592653589793, true)"#
         );
 
         let tuple_nested = ((1, 2), ("a", "b"));
-        assert_eq!(tuple_nested.spore_print(), "((1, 2), (a, b))");
+        assert_eq!(tuple_nested.spore_print(), r#"((1, 2), ("a", "b"))"#);
 
         let tuple_complex = (42, "hello", vec![1, 2, 3]);
-        assert_eq!(tuple_complex.spore_print(), "(42, hello, [1, 2, 3])");
+        assert_eq!(tuple_complex.spore_print(), r#"(42, "hello", [1, 2, 3])"#);
     }
 
     /// Tests `SporePrint` implementation for an empty tuple `()`
@@ -385,14 +1510,14 @@ mod tests {
     fn test_tuple_with_references() {
         let value = 42;
         let reference_tuple = (&value, &"hello");
-        assert_eq!(reference_tuple.spore_print(), "(42, hello)");
+        assert_eq!(reference_tuple.spore_print(), r#"(42, "hello")"#);
     }
 
     /// Tests `SporePrint` implementation for deeply nested tuples
     #[test]
     fn test_nested_tuples() {
         let tuple = ((1, 2), (3, (4, (5, "deep"))));
-        assert_eq!(tuple.spore_print(), "((1, 2), (3, (4, (5, deep))))");
+        assert_eq!(tuple.spore_print(), r#"((1, 2), (3, (4, (5, "deep"))))"#);
     }
 
     /// Tests `SporePrint` implementation for a tuple with `Option` values, all `None`
@@ -411,7 +1536,10 @@ mod tests {
             Option::<()>::None,
             Result::<f64, &str>::Err("error"),
         );
-        assert_eq!(tuple.spore_print(), "(string, [1, 2, 3], None, Err(error))");
+        assert_eq!(
+            tuple.spore_print(),
+            r#"("string", [1, 2, 3], None, Err("error"))"#
+        );
     }
 
     /// Tests `SporePrint` implementation for slices
@@ -421,6 +1549,18 @@ mod tests {
         assert_eq!(slice.spore_print(), "[1, 2, 3]");
     }
 
+    /// Tests `SporePrint` implementation for unsized slices and strings, and for boxed slices
+    #[test]
+    fn test_unsized() {
+        let slice: &[i32] = &[1, 2, 3];
+        assert_eq!(<[i32]>::spore_print(slice), "[1, 2, 3]");
+        assert_eq!(str::spore_print("text"), r#""text""#);
+
+        let boxed: Box<[&str]> = vec!["a", "b"].into_boxed_slice();
+        assert_eq!(boxed.spore_print(), r#"["a", "b"]"#);
+        assert_eq!(boxed.to_spore_value().spore_print(), boxed.spore_print());
+    }
+
     /// Tests `SporePrint` implementation for arrays
     #[test]
     fn test_array() {
@@ -437,7 +1577,7 @@ mod tests {
 
         let value = String::from("hello");
         let reference: &String = &value;
-        assert_eq!(reference.spore_print(), "hello");
+        assert_eq!(reference.spore_print(), r#""hello""#);
 
         let value = vec![1, 2, 3];
         let reference: &Vec<i32> = &value;
@@ -449,7 +1589,185 @@ mod tests {
 
         let value = (42, "hello");
         let reference: &(i32, &str) = &value;
-        assert_eq!(reference.spore_print(), "(42, hello)");
+        assert_eq!(reference.spore_print(), r#"(42, "hello")"#);
+    }
+
+    /// Tests `SporePrint` implementation for smart pointers, which print as the value they point to
+    #[test]
+    fn test_smart_pointers() {
+        assert_eq!(Box::new(1).spore_print(), "1");
+        assert_eq!(Rc::new("a").spore_print(), r#""a""#);
+        assert_eq!(Arc::new(vec![1, 2]).spore_print(), "[1, 2]");
+        assert_eq!(Arc::<str>::from("text").spore_print(), r#""text""#);
+        assert_eq!(Box::pin(Some(3)).spore_print(), "Some(3)");
+        assert_eq!(Cow::<str>::Borrowed("a").spore_print(), r#""a""#);
+        assert_eq!(Cow::<[i32]>::Owned(vec![1]).spore_print(), "[1]");
+
+        let mut value = (1, 'x');
+        let reference = &mut value;
+        assert_eq!(reference.spore_print(), "(1, 'x')");
+        assert_eq!(reference.to_spore_value(), value.to_spore_value());
+    }
+
+    /// Tests `SporePrint` implementation for cells, with the markers printed when the value cannot be read
+    #[test]
+    fn test_cells() {
+        assert_eq!(Cell::new(5).spore_print(), "5");
+
+        let cell = RefCell::new(vec![1]);
+        assert_eq!(cell.spore_print(), "[1]");
+        let borrow = cell.borrow_mut();
+        assert_eq!(cell.spore_print(), "<borrowed>");
+        assert_eq!(
+            cell.to_spore_value(),
+            SporeValue::Marker("borrowed".to_string())
+        );
+        drop(borrow);
+
+        let once = OnceCell::new();
+        assert_eq!(once.spore_print(), "<uninit>");
+        once.set("set").unwrap();
+        assert_eq!(once.spore_print(), r#""set""#);
+        let once = OnceLock::from(2);
+        assert_eq!(once.to_spore_value(), SporeValue::Int(2));
+    }
+
+    /// Tests `SporePrint` implementation for locks, which are never waited for
+    #[test]
+    fn test_locks() {
+        let mutex = Arc::new(Mutex::new(1));
+        assert_eq!(mutex.spore_print(), "1");
+        let guard = mutex.lock().unwrap();
+        assert_eq!(mutex.spore_print(), "<locked>");
+        drop(guard);
+
+        let poisoner = Arc::clone(&mutex);
+        std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poisoning the lock");
+        })
+        .join()
+        .unwrap_err();
+        assert_eq!(mutex.spore_print(), "<poisoned>");
+
+        let lock = RwLock::new("a");
+        let reader = lock.read().unwrap();
+        assert_eq!(lock.spore_print(), r#""a""#);
+        drop(reader);
+        let writer = lock.write().unwrap();
+        assert_eq!(
+            lock.to_spore_value(),
+            SporeValue::Marker("locked".to_string())
+        );
+        drop(writer);
+    }
+
+    /// Tests `SporePrint` implementation for weak pointers
+    #[test]
+    fn test_weak_pointers() {
+        let strong = Rc::new(1);
+        let weak = Rc::downgrade(&strong);
+        assert_eq!(weak.spore_print(), "1");
+        drop(strong);
+        assert_eq!(weak.spore_print(), "<dropped>");
+
+        let strong = Arc::new("a");
+        let weak = Arc::downgrade(&strong);
+        assert_eq!(weak.to_spore_value(), SporeValue::Str("a".to_string()));
+        drop(strong);
+        assert_eq!(
+            weak.to_spore_value(),
+            SporeValue::Marker("dropped".to_string())
+        );
+    }
+
+    /// Tests that fingerprints hash the canonical spore print
+    #[test]
+    fn test_fingerprints() {
+        let value = vec![Some(1), None];
+        let hex: String = value
+            .spore_fingerprint()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert_eq!(
+            hex,
+            "68a2bb1f0be2d9a908eef3f2df7beaf93dbe3454f40829ce1140e034c5fa8253"
+        );
+        assert_eq!(value.spore_fingerprint64(), 0xc5a06495c3eda7ad);
+
+        let forward: HashMap<_, _> = (0..100).map(|i| (i, i * 2)).collect();
+        let backward: HashMap<_, _> = (0..100).rev().map(|i| (i, i * 2)).collect();
+        assert_eq!(forward.spore_fingerprint(), backward.spore_fingerprint());
+        assert_eq!(
+            forward.spore_fingerprint64(),
+            backward.spore_fingerprint64()
+        );
+        assert_ne!(
+            forward.spore_fingerprint(),
+            HashMap::<i32, i32>::new().spore_fingerprint()
+        );
+        assert_ne!(
+            vec!["a, b"].spore_fingerprint(),
+            vec!["a", "b"].spore_fingerprint()
+        );
+    }
+
+    /// Tests that `spore_print_to` appends to the writer and matches `spore_print`
+    #[test]
+    fn test_spore_print_to() {
+        let value = (vec![Some("a")], HashMap::from([(1, 'x')]), 1..=3);
+        let mut out = String::from("value: ");
+        value.spore_print_to(&mut out).unwrap();
+        assert_eq!(out, format!("value: {}", value.spore_print()));
+        assert_eq!(out, r#"value: ([Some("a")], {1: 'x'}, 1..=3)"#);
+    }
+
+    /// Tests that `to_spore_value` builds the expected tree and prints back to the same text
+    #[test]
+    fn test_to_spore_value() {
+        let value = (
+            vec![Some('a'), None],
+            HashMap::from([("b", Ok::<u8, ()>(2)), ("a", Err(()))]),
+            HashSet::from([3, 1, 2]),
+            1.5f32..2.25,
+            (-1i64,),
+        );
+        assert_eq!(value.to_spore_value().spore_print(), value.spore_print());
+        assert_eq!(
+            value.to_spore_value(),
+            SporeValue::Tuple(vec![
+                SporeValue::List(vec![
+                    SporeValue::Option(Some(Box::new(SporeValue::Char('a')))),
+                    SporeValue::Option(None),
+                ]),
+                SporeValue::Map(vec![
+                    (
+                        SporeValue::Str("a".to_string()),
+                        SporeValue::Result(Err(Box::new(SporeValue::Unit)))
+                    ),
+                    (
+                        SporeValue::Str("b".to_string()),
+                        SporeValue::Result(Ok(Box::new(SporeValue::Int(2))))
+                    ),
+                ]),
+                SporeValue::List(vec![
+                    SporeValue::Int(1),
+                    SporeValue::Int(2),
+                    SporeValue::Int(3)
+                ]),
+                SporeValue::Range {
+                    start: Some(Box::new(SporeValue::Float(1.5))),
+                    end: Some(Box::new(SporeValue::Float(2.25))),
+                    inclusive: false,
+                },
+                SporeValue::Tuple(vec![SporeValue::Int(-1)]),
+            ])
+        );
+
+        assert_eq!(0.1f32.to_spore_value().spore_print(), "0.1");
+        assert_eq!(u64::MAX.to_spore_value(), SporeValue::Int(u64::MAX.into()));
+        assert_eq!("a\"b".to_spore_value().spore_print(), "a\"b".spore_print());
     }
 
     /// Tests `SporePrint` implementation for custom types
@@ -468,5 +1786,31 @@ mod tests {
         let value = Custom(1);
         let reference: &Custom = &value;
         assert_eq!(reference.spore_print(), "Custom(1)");
+
+        let mut out = String::new();
+        vec![Custom(3)].spore_print_to(&mut out).unwrap();
+        assert_eq!(out, "[Custom(3)]");
+
+        assert_eq!(
+            Custom(4).to_spore_value(),
+            SporeValue::TupleStruct {
+                name: "Custom".to_string(),
+                fields: vec![SporeValue::Int(4)],
+            }
+        );
+
+        struct Freeform;
+        impl SporePrint for Freeform {
+            fn spore_print(&self) -> String {
+                "not < spore > text".to_string()
+            }
+        }
+
+        let value = vec![Freeform].to_spore_value();
+        assert_eq!(
+            value,
+            SporeValue::List(vec![SporeValue::Raw("not < spore > text".to_string())])
+        );
+        assert_eq!(value.spore_print(), "[not < spore > text]");
     }
 }
@@ -1,40 +1,306 @@
-/// Returns the formatted string representation of the given arguments using the `SporePrint` trait.
+use crate::SporePrint;
+use std::fmt;
+
+/// Prints the spore print of the given values to the standard output.
 ///
-/// This macro uses the `SporePrint` trait to generate a consistent and immutable string representation
-/// of the provided arguments and returns it as a `String`.
+/// The values are separated by a space, or by the string given as `sep = "...";` before them. Like
+/// [`print!`], nothing is added at the end, see [`sprintln!`] to end the line.
 ///
 /// # Examples
 ///
 /// ```
 /// use spore_print::sprint;
 ///
-/// let value = 42;
-/// let result = sprint!(value);  // result: "42"
+/// let name = "ada";
+/// sprint!(name);  // Output: "ada"
+/// sprint!(name, 42, vec![1, 2]);  // Output: "ada" 42 [1, 2]
+/// sprint!(sep = " | "; name, 42);  // Output: "ada" | 42
 /// ```
 #[macro_export]
 macro_rules! sprint {
-    ($($arg:tt)*) => {
-        spore_print::SporePrint::spore_print(&$($arg)*)
+    (sep = $sep:expr; $($arg:expr),+ $(,)?) => {
+        ::std::print!("{}", $crate::__sjoin!($sep; $($arg),+))
+    };
+    ($($arg:expr),+ $(,)?) => {
+        $crate::sprint!(sep = " "; $($arg),+)
     };
 }
 
-/// Prints the formatted string representation of the given arguments using the `SporePrint` trait,
-/// followed by a newline.
+/// Prints the spore print of the given values to the standard output, followed by a newline.
 ///
-/// This macro is a wrapper around `sprint!` that adds a newline after printing.
+/// The values are separated like in [`sprint!`]. Without values, only the newline is printed.
 ///
 /// # Examples
 ///
 /// ```
-/// use spore_print::{sprint, sprintln};
+/// use spore_print::sprintln;
 ///
-/// let value = 42;
-/// sprintln!(value);  // Output: 42 (with newline)
+/// let value = Some(42);
+/// sprintln!(value);  // Output: Some(42)
+/// sprintln!(sep = ", "; value, None::<i32>);  // Output: Some(42), None
+/// sprintln!();  // Output: an empty line
 /// ```
 #[macro_export]
 macro_rules! sprintln {
-    ($($arg:tt)*) => {
-        sprint!($($arg)*);
-        println!();
+    () => {
+        ::std::println!()
+    };
+    (sep = $sep:expr; $($arg:expr),+ $(,)?) => {
+        ::std::println!("{}", $crate::__sjoin!($sep; $($arg),+))
+    };
+    ($($arg:expr),+ $(,)?) => {
+        $crate::sprintln!(sep = " "; $($arg),+)
+    };
+}
+
+/// Prints the spore print of the given values to the standard error.
+///
+/// This is the standard error counterpart of [`sprint!`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::seprint;
+///
+/// seprint!("retrying", 3);  // Error output: "retrying" 3
+/// ```
+#[macro_export]
+macro_rules! seprint {
+    (sep = $sep:expr; $($arg:expr),+ $(,)?) => {
+        ::std::eprint!("{}", $crate::__sjoin!($sep; $($arg),+))
+    };
+    ($($arg:expr),+ $(,)?) => {
+        $crate::seprint!(sep = " "; $($arg),+)
+    };
+}
+
+/// Prints the spore print of the given values to the standard error, followed by a newline.
+///
+/// This is the standard error counterpart of [`sprintln!`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::seprintln;
+///
+/// seprintln!("retrying", 3);  // Error output: "retrying" 3
+/// ```
+#[macro_export]
+macro_rules! seprintln {
+    () => {
+        ::std::eprintln!()
+    };
+    (sep = $sep:expr; $($arg:expr),+ $(,)?) => {
+        ::std::eprintln!("{}", $crate::__sjoin!($sep; $($arg),+))
+    };
+    ($($arg:expr),+ $(,)?) => {
+        $crate::seprintln!(sep = " "; $($arg),+)
+    };
+}
+
+/// Creates a `String` like [`format!`], formatting the arguments with `SporePrint` instead of `Display`.
+///
+/// Positional and named arguments are supported, as well as width, fill and alignment, which apply to the
+/// whole spore print. Identifiers cannot be captured inline in the format string, as `format!` does for
+/// `"{value}"`, because they would bypass `SporePrint`: this fails to compile, and the value has to be passed as a
+/// named argument such as `value = value` instead.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::sformat;
+///
+/// let expected = "ok";
+/// let actual = Some("ok");
+/// assert_eq!(sformat!("{} vs {}", expected, actual), r#""ok" vs Some("ok")"#);
+/// assert_eq!(sformat!("{0}/{0}", 'x'), "'x'/'x'");
+/// assert_eq!(sformat!("[{value:>6}]", value = (1, 2)), "[(1, 2)]");
+/// assert_eq!(sformat!("[{:<8}]", vec![1]), "[[1]     ]");
+/// ```
+///
+/// ```compile_fail
+/// use spore_print::sformat;
+///
+/// let value = "ok";
+/// sformat!("{value}");  // error: there is no argument named `value`
+/// ```
+#[macro_export]
+macro_rules! sformat {
+    // The format string goes through `concat!` so that `format!` refuses to capture identifiers inline.
+    ($fmt:literal $(,)?) => {
+        ::std::format!(::std::concat!($fmt))
+    };
+    ($fmt:literal, $($args:tt)+) => {
+        $crate::__sformat!([$fmt] [] $($args)+)
     };
 }
+
+/// Prints the source location, the expression and the spore print of the given values to the standard error,
+/// and returns the values.
+///
+/// Like [`dbg!`], the value is moved in and returned, so the macro can wrap an expression in place, and several
+/// values are returned as a tuple. Starting the arguments with `pretty;` prints the values with
+/// [`spore_print_pretty`](crate::SporePrint::spore_print_pretty) instead of on a single line.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::spore_dbg;
+///
+/// let scores = spore_dbg!(vec![3, 1, 2]);  // Error output: [src/main.rs:4:14] vec![3, 1, 2] = [3, 1, 2]
+/// assert_eq!(scores, vec![3, 1, 2]);
+///
+/// let (low, high) = spore_dbg!(1, "high");  // Error output: one line for `1`, one for `"high"`
+/// assert_eq!((low, high), (1, "high"));
+///
+/// let nested = spore_dbg!(pretty; vec![Some(("a long label", 1)); 4]);
+/// assert_eq!(nested.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! spore_dbg {
+    () => {
+        ::std::eprintln!("[{}:{}:{}]", ::std::file!(), ::std::line!(), ::std::column!())
+    };
+    (pretty; $val:expr $(,)?) => {
+        // `match` keeps the temporaries of `$val` alive for the whole print, like `dbg!`.
+        match $val {
+            tmp => {
+                ::std::eprintln!(
+                    "[{}:{}:{}] {} = {}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::column!(),
+                    ::std::stringify!($val),
+                    $crate::SporePrint::spore_print_pretty(&tmp),
+                );
+                tmp
+            }
+        }
+    };
+    (pretty; $($val:expr),+ $(,)?) => {
+        ($($crate::spore_dbg!(pretty; $val)),+,)
+    };
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                ::std::eprintln!(
+                    "[{}:{}:{}] {} = {}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::column!(),
+                    ::std::stringify!($val),
+                    $crate::__private::Printed(&tmp),
+                );
+                tmp
+            }
+        }
+    };
+    ($($val:expr),+ $(,)?) => {
+        ($($crate::spore_dbg!($val)),+,)
+    };
+}
+
+/// Wraps every argument of `sformat!` in `__private::Printed`, keeping the names of named arguments.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sformat {
+    ([$fmt:literal] [$($done:tt)*]) => {
+        ::std::format!(::std::concat!($fmt), $($done)*)
+    };
+    ([$fmt:literal] [$($done:tt)*] $name:ident = $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::__sformat!(
+            [$fmt] [$($done)* $name = $crate::__private::Printed(&$arg),] $($($rest)*)?
+        )
+    };
+    ([$fmt:literal] [$($done:tt)*] $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::__sformat!([$fmt] [$($done)* $crate::__private::Printed(&$arg),] $($($rest)*)?)
+    };
+}
+
+/// Evaluates to a `Display` value showing the spore prints of the arguments separated by `sep`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sjoin {
+    ($sep:expr; $($arg:expr),+) => {
+        $crate::__private::Joined {
+            sep: &$sep,
+            items: &[$(&$crate::__private::Printed(&$arg)),+],
+        }
+    };
+}
+
+/// Displays the spore print of the wrapped value, for the formatting macros.
+#[doc(hidden)]
+pub struct Printed<'a, T: ?Sized>(pub &'a T);
+
+impl<T: SporePrint + ?Sized> fmt::Display for Printed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Padding needs the length of the print, so it is only built as a string when a width is set.
+        if f.width().is_some() {
+            f.pad(&self.0.spore_print())
+        } else {
+            self.0.spore_print_to(f)
+        }
+    }
+}
+
+/// Displays a list of values separated by `sep`, for the printing macros.
+#[doc(hidden)]
+pub struct Joined<'a> {
+    pub sep: &'a str,
+    pub items: &'a [&'a dyn fmt::Display],
+}
+
+impl fmt::Display for Joined<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                f.write_str(self.sep)?;
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Tests that `sformat!` formats its arguments with `SporePrint`
+    #[test]
+    fn test_sformat() {
+        let name = "ada";
+        assert_eq!(sformat!("plain"), "plain");
+        assert_eq!(sformat!("{}", name), r#""ada""#);
+        assert_eq!(sformat!("{} = {}", name, name.len(),), r#""ada" = 3"#);
+        assert_eq!(
+            sformat!("{a}, {b}, {}", 'c', b = vec![2], a = (1,)),
+            "(1,), [2], 'c'"
+        );
+        assert_eq!(sformat!("{:^9}", Some(1)), " Some(1) ");
+    }
+
+    /// Tests that `spore_dbg!` returns its arguments
+    #[test]
+    fn test_spore_dbg() {
+        let text = String::from("moved");
+        let moved: String = spore_dbg!(text);
+        assert_eq!(moved, "moved");
+        assert_eq!(spore_dbg!(1 + 1, "two",), (2, "two"));
+        assert_eq!(spore_dbg!(pretty; vec![(1, 'a')]), vec![(1, 'a')]);
+        assert_eq!(spore_dbg!(pretty; 1, 2), (1, 2));
+        spore_dbg!();
+    }
+
+    /// Tests the text written by the printing macros
+    #[test]
+    fn test_sjoin() {
+        let value = vec![Some(1), None];
+        assert_eq!(__sjoin!(" "; value).to_string(), "[Some(1), None]");
+        assert_eq!(
+            __sjoin!(", "; value, "x", 2.5).to_string(),
+            r#"[Some(1), None], "x", 2.5"#
+        );
+        let sep = String::from("");
+        assert_eq!(__sjoin!(sep; 1, 2).to_string(), "12");
+    }
+}
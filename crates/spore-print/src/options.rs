@@ -0,0 +1,111 @@
+use std::cell::Cell;
+use std::sync::atomic::Ordering;
+
+/// Options for [`SporePrint::spore_print_with`](crate::SporePrint::spore_print_with).
+///
+/// The default options give the canonical form. Options apply to the current thread while they are in
+/// [`scope`](PrintOptions::scope), so they reach every nested value, including the fields of derived types.
+/// They affect the printed text only: [`SporeValue`](crate::SporeValue) does not record numeric types, and the
+/// parser reads suffixed literals as plain numbers. For the same reason
+/// [`spore_print_pretty`](crate::SporePrint::spore_print_pretty), which lays out the `SporeValue` of a value,
+/// prints numbers without their suffix even when `type_suffixes` is in scope.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{PrintOptions, SporePrint};
+///
+/// let value = (42u8, 42i64, 1.5f32);
+/// assert_eq!(value.spore_print(), "(42, 42, 1.5)");
+///
+/// let options = PrintOptions {
+///     type_suffixes: true,
+///     ..PrintOptions::default()
+/// };
+/// assert_eq!(value.spore_print_with(&options), "(42u8, 42i64, 1.5f32)");
+/// assert_ne!(
+///     options.scope(|| 42u8.spore_fingerprint64()),
+///     options.scope(|| 42i64.spore_fingerprint64())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintOptions {
+    /// Whether integers and finite floats are followed by their type suffix, as in `42u8` or `1.5f32`, so that
+    /// values of different numeric types print differently. Pretty printing does not add them.
+    pub type_suffixes: bool,
+    /// The ordering atomics are loaded with. `Release` and `AcqRel` are not valid for a load and are replaced
+    /// with `Relaxed` and `Acquire`.
+    pub atomic_ordering: Ordering,
+}
+
+impl PrintOptions {
+    const DEFAULT: PrintOptions = PrintOptions {
+        type_suffixes: false,
+        atomic_ordering: Ordering::SeqCst,
+    };
+
+    /// Runs `f` with these options applied to everything printed on the current thread, then restores the
+    /// options that were in scope before, even if `f` panics.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _restore = Restore(CURRENT.with(|current| current.replace(*self)));
+        f()
+    }
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions::DEFAULT
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<PrintOptions> = const { Cell::new(PrintOptions::DEFAULT) };
+}
+
+/// Puts back the options that were in scope when it is dropped.
+struct Restore(PrintOptions);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.0));
+    }
+}
+
+/// Returns the options in scope on the current thread.
+pub(crate) fn current() -> PrintOptions {
+    CURRENT.with(Cell::get)
+}
+
+/// Returns the ordering to load atomics with, as a valid load ordering.
+pub(crate) fn load_ordering() -> Ordering {
+    match current().atomic_ordering {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        ordering => ordering,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that options are restored when a scope ends, including by a panic
+    #[test]
+    fn test_scope() {
+        let suffixed = PrintOptions {
+            type_suffixes: true,
+            atomic_ordering: Ordering::AcqRel,
+        };
+        suffixed.scope(|| {
+            assert_eq!(current(), suffixed);
+            assert_eq!(load_ordering(), Ordering::Acquire);
+            PrintOptions::default().scope(|| assert!(!current().type_suffixes));
+            assert!(current().type_suffixes);
+        });
+        assert_eq!(current(), PrintOptions::default());
+
+        let result = std::panic::catch_unwind(|| suffixed.scope(|| panic!("inside a scope")));
+        assert!(result.is_err());
+        assert_eq!(current(), PrintOptions::default());
+    }
+}
@@ -0,0 +1,810 @@
+//! Parsing of spore print text back into a [`SporeValue`] tree.
+//!
+//! The parser accepts everything the built-in `SporePrint` implementations and `#[derive(SporePrint)]` emit:
+//! literals, including numbers with a type suffix such as `42u8` or `1.5f32`, durations `1.5s` and timestamps
+//! `2024-05-06T07:08:09Z`, lists `[..]`, maps `{k: v}`, tuples `(..)`, `Some(..)`/`None`, `Ok(..)`/`Err(..)`, ranges
+//! `a..b`/`a..=b`, structs `Name { f: v }`, tuple structs `Name(..)`, unit structs `Name`, enum variants
+//! `Enum::Variant` and markers `<redacted>`. Whitespace between tokens and trailing commas are ignored.
+//!
+//! # Examples
+//!
+//! ```
+//! use spore_print::parse::parse;
+//! use spore_print::SporeValue;
+//!
+//! let value = parse(r#"[Some("a"), None]"#).unwrap();
+//! assert_eq!(
+//!     value,
+//!     SporeValue::List(vec![
+//!         SporeValue::Option(Some(Box::new(SporeValue::Str("a".to_string())))),
+//!         SporeValue::Option(None),
+//!     ])
+//! );
+//!
+//! let error = parse("[1, 2").unwrap_err();
+//! assert_eq!(error.to_string(), "expected `,` or `]`, found end of input at line 1, column 6");
+//! ```
+
+use crate::{time, SporeValue};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum nesting depth accepted by the parser, to keep recursion bounded on hostile input.
+const MAX_DEPTH: usize = 256;
+
+/// Type suffixes a number may end with, printed with [`PrintOptions::type_suffixes`](crate::PrintOptions).
+const NUMBER_SUFFIXES: [&str; 14] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64",
+];
+
+/// Parses spore print text into a [`SporeValue`].
+pub fn parse(input: &str) -> Result<SporeValue, ParseError> {
+    let mut parser = Parser::new(input);
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(_) => Err(parser.unexpected("end of input")),
+    }
+}
+
+impl FromStr for SporeValue {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+/// An error produced when spore print text cannot be parsed, with the 1-based position it occurred at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// The 1-based line of the offending input.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The 1-based column, counted in chars, of the offending input.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// A description of what went wrong, without the position.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A recursive descent parser over the spore print grammar.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+    column: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            pos: 0,
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consumes `token` if the input continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            token.chars().for_each(|_| {
+                self.bump();
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes `word` if the input continues with it and the word is not followed by more identifier chars.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let rest = self.rest();
+        rest.starts_with(word)
+            && !rest[word.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+            && self.eat(word)
+    }
+
+    /// Skips whitespace and consumes `token`, failing if the input continues with something else.
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", token)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+
+    /// Builds an error describing what was `expected` and what the input continues with instead.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some('\n') => "a line break".to_string(),
+            Some(c) => format!("`{}`", c),
+            None => "end of input".to_string(),
+        };
+        self.error(format!("expected {}, found {}", expected, found))
+    }
+
+    /// Parses a value, including ranges, whose bounds are plain values.
+    fn parse_value(&mut self) -> Result<SporeValue, ParseError> {
+        self.skip_whitespace();
+        let start = if self.rest().starts_with("..") {
+            None
+        } else {
+            let start = self.parse_primary()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with("..") {
+                return Ok(start);
+            }
+            Some(Box::new(start))
+        };
+
+        self.eat("..");
+        let inclusive = self.eat("=");
+        self.skip_whitespace();
+        let end = match self.peek() {
+            None | Some(',' | ')' | ']' | '}' | ':') if !inclusive => None,
+            _ => Some(Box::new(self.parse_primary()?)),
+        };
+
+        Ok(SporeValue::Range {
+            start,
+            end,
+            inclusive,
+        })
+    }
+
+    /// Parses a value that is not a range.
+    fn parse_primary(&mut self) -> Result<SporeValue, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.nested(|parser| {
+                parser.bump();
+                let (items, _) = parser.parse_sequence(']', Parser::parse_value)?;
+                Ok(SporeValue::List(items))
+            }),
+            Some('{') => self.nested(|parser| {
+                parser.bump();
+                let (entries, _) = parser.parse_sequence('}', |parser| {
+                    let key = parser.parse_value()?;
+                    parser.expect(":")?;
+                    Ok((key, parser.parse_value()?))
+                })?;
+                Ok(SporeValue::Map(entries))
+            }),
+            Some('(') => self.nested(Parser::parse_tuple),
+            Some('"') => self.parse_string(),
+            Some('\'') => self.parse_char(),
+            Some('<') => self.parse_marker(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c == '_' || c.is_alphabetic() => self.parse_named(),
+            _ => Err(self.unexpected("a value")),
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, failing once the nesting limit is exceeded.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(format!("nesting deeper than {} levels", MAX_DEPTH)));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Parses comma-separated items up to and including `close`, after the opening delimiter was consumed.
+    /// Also returns whether the last item was followed by a trailing comma.
+    fn parse_sequence<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<(Vec<T>, bool), ParseError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.bump();
+                return Ok((items, true));
+            }
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(c) if c == close => {
+                    self.bump();
+                    return Ok((items, false));
+                }
+                _ => return Err(self.unexpected(&format!("`,` or `{}`", close))),
+            }
+        }
+    }
+
+    fn parse_tuple(&mut self) -> Result<SporeValue, ParseError> {
+        self.bump();
+        let (line, column) = (self.line, self.column);
+        let (mut items, trailing_comma) = self.parse_sequence(')', Parser::parse_value)?;
+        match items.len() {
+            0 => Ok(SporeValue::Unit),
+            1 if !trailing_comma => Err(ParseError {
+                line,
+                column,
+                message: "expected a trailing `,` in a single-element tuple".to_string(),
+            }),
+            1 => Ok(SporeValue::Tuple(vec![items.remove(0)])),
+            _ => Ok(SporeValue::Tuple(items)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<SporeValue, ParseError> {
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(SporeValue::Str(text));
+                }
+                Some('\\') => text.push(self.parse_escape()?),
+                Some(c) => {
+                    self.bump();
+                    text.push(c);
+                }
+                None => return Err(self.unexpected("`\"`")),
+            }
+        }
+    }
+
+    fn parse_char(&mut self) -> Result<SporeValue, ParseError> {
+        self.bump();
+        let c = match self.peek() {
+            Some('\\') => self.parse_escape()?,
+            Some(c) if c != '\'' => {
+                self.bump();
+                c
+            }
+            _ => return Err(self.unexpected("a char")),
+        };
+        if !self.eat("'") {
+            return Err(self.unexpected("`'`"));
+        }
+        Ok(SporeValue::Char(c))
+    }
+
+    /// Parses a marker `<text>`, whose text runs up to the first `>` on the same line.
+    fn parse_marker(&mut self) -> Result<SporeValue, ParseError> {
+        self.bump();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !matches!(c, '<' | '>' | '\n')) {
+            self.bump();
+        }
+        let text = self.input[start..self.pos].to_string();
+        if !self.eat(">") {
+            return Err(self.unexpected("`>`"));
+        }
+        Ok(SporeValue::Marker(text))
+    }
+
+    /// Parses an escape sequence starting at the backslash.
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+        let escaped = match self.bump() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some(c @ ('\\' | '"' | '\'')) => c,
+            Some('u') => {
+                self.expect("{")?;
+                let digits_start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    self.bump();
+                }
+                let digits = &self.input[digits_start..self.pos];
+                self.expect("}")?;
+                u32::from_str_radix(digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| ParseError {
+                        line,
+                        column,
+                        message: format!("invalid unicode escape `\\u{{{}}}`", digits),
+                    })?
+            }
+            _ => {
+                return Err(ParseError {
+                    line,
+                    column,
+                    message: "unknown escape sequence".to_string(),
+                })
+            }
+        };
+        Ok(escaped)
+    }
+
+    fn parse_number(&mut self) -> Result<SporeValue, ParseError> {
+        let (line, column) = (self.line, self.column);
+        let start = self.pos;
+        let negative = self.eat("-");
+        if self.eat("inf") {
+            return Ok(SporeValue::Float(if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }));
+        }
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.unexpected("a digit"));
+        }
+        self.skip_digits();
+        let error = |message: &str, text: &str| ParseError {
+            line,
+            column,
+            message: format!("{} `{}`", message, text),
+        };
+
+        // A timestamp continues its year with `-` and the month
+        if self.rest().starts_with('-')
+            && self.rest()[1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            while self
+                .peek()
+                .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | ':' | '.' | 'T' | 'Z'))
+            {
+                self.bump();
+            }
+            let text = &self.input[start..self.pos];
+            return time::parse_timestamp(text)
+                .map(SporeValue::Timestamp)
+                .ok_or_else(|| error("invalid timestamp", text));
+        }
+
+        let mut is_float = false;
+        if self.rest().starts_with('.')
+            && self.rest()[1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            self.bump();
+            self.skip_digits();
+            is_float = true;
+        }
+        let text = &self.input[start..self.pos];
+        if self.eat_word("s") {
+            return time::parse_duration(text)
+                .map(SporeValue::Duration)
+                .ok_or_else(|| error("invalid duration", &self.input[start..self.pos]));
+        }
+        if self.rest().starts_with(['e', 'E']) {
+            self.bump();
+            if !self.eat("-") {
+                self.eat("+");
+            }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.unexpected("a digit"));
+            }
+            self.skip_digits();
+            is_float = true;
+        }
+
+        let text = &self.input[start..self.pos];
+        if let Some(suffix) = NUMBER_SUFFIXES.iter().find(|suffix| self.eat_word(suffix)) {
+            is_float |= suffix.starts_with('f');
+        }
+        if is_float {
+            return text
+                .parse()
+                .map(SporeValue::Float)
+                .map_err(|_| error("invalid float", text));
+        }
+        match text.parse() {
+            Ok(int) => Ok(SporeValue::Int(int)),
+            Err(_) if !negative => text
+                .parse()
+                .map(SporeValue::UInt)
+                .map_err(|_| error("integer out of range", text)),
+            Err(_) => Err(error("integer out of range", text)),
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+    }
+
+    fn parse_ident(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        self.eat("r#");
+        match self.peek() {
+            Some(c) if c == '_' || c.is_alphabetic() => {}
+            _ => return Err(self.unexpected("an identifier")),
+        }
+        while self.peek().is_some_and(|c| c == '_' || c.is_alphanumeric()) {
+            self.bump();
+        }
+        Ok(&self.input[start..self.pos])
+    }
+
+    /// Parses values introduced by an identifier: keywords, `Option`/`Result` constructors, structs and variants.
+    fn parse_named(&mut self) -> Result<SporeValue, ParseError> {
+        let name = self.parse_ident()?;
+        match name {
+            "true" => return Ok(SporeValue::Bool(true)),
+            "false" => return Ok(SporeValue::Bool(false)),
+            "NaN" => return Ok(SporeValue::Float(f64::NAN)),
+            "inf" => return Ok(SporeValue::Float(f64::INFINITY)),
+            "None" => return Ok(SporeValue::Option(None)),
+            "Some" | "Ok" | "Err" => {
+                let inner = self.nested(|parser| {
+                    parser.expect("(")?;
+                    let inner = parser.parse_value()?;
+                    parser.skip_whitespace();
+                    parser.eat(",");
+                    parser.expect(")")?;
+                    Ok(Box::new(inner))
+                })?;
+                return Ok(match name {
+                    "Some" => SporeValue::Option(Some(inner)),
+                    "Ok" => SporeValue::Result(Ok(inner)),
+                    _ => SporeValue::Result(Err(inner)),
+                });
+            }
+            _ => {}
+        }
+
+        self.skip_whitespace();
+        if self.eat("::") {
+            let variant = self.parse_ident()?;
+            let variant = self.parse_struct_body(variant)?;
+            return Ok(SporeValue::Variant {
+                enum_name: name.to_string(),
+                variant: Box::new(variant),
+            });
+        }
+        self.parse_struct_body(name)
+    }
+
+    /// Parses the optional `(..)` or `{ .. }` body following a struct or variant name.
+    fn parse_struct_body(&mut self, name: &str) -> Result<SporeValue, ParseError> {
+        self.skip_whitespace();
+        let name = name.to_string();
+        match self.peek() {
+            Some('(') => self.nested(|parser| {
+                parser.bump();
+                let (fields, _) = parser.parse_sequence(')', Parser::parse_value)?;
+                Ok(SporeValue::TupleStruct { name, fields })
+            }),
+            Some('{') => self.nested(|parser| {
+                parser.bump();
+                let (fields, _) = parser.parse_sequence('}', |parser| {
+                    let field = parser.parse_ident()?.to_string();
+                    parser.expect(":")?;
+                    Ok((field, parser.parse_value()?))
+                })?;
+                Ok(SporeValue::Struct { name, fields })
+            }),
+            _ => Ok(SporeValue::UnitStruct(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i128) -> SporeValue {
+        SporeValue::Int(value)
+    }
+
+    fn string(value: &str) -> SporeValue {
+        SporeValue::Str(value.to_string())
+    }
+
+    /// Tests parsing of literals
+    #[test]
+    fn test_literals() {
+        assert_eq!(parse("42").unwrap(), int(42));
+        assert_eq!(parse("-7").unwrap(), int(-7));
+        assert_eq!(
+            parse("340282366920938463463374607431768211455").unwrap(),
+            SporeValue::UInt(u128::MAX)
+        );
+        assert_eq!(parse("1.5").unwrap(), SporeValue::Float(1.5));
+        assert_eq!(parse("-2.5e-3").unwrap(), SporeValue::Float(-2.5e-3));
+        assert_eq!(parse("42u8").unwrap(), int(42));
+        assert_eq!(parse("-1i128").unwrap(), int(-1));
+        assert_eq!(parse("1.5f32").unwrap(), SporeValue::Float(1.5));
+        assert_eq!(parse("2f64").unwrap(), SporeValue::Float(2.0));
+        assert_eq!(parse("1e16f64").unwrap(), SporeValue::Float(1e16));
+        assert!(parse("1u7").is_err());
+        assert_eq!(
+            parse("1.25s").unwrap(),
+            SporeValue::Duration(std::time::Duration::from_millis(1250))
+        );
+        assert_eq!(
+            parse("1970-01-02T00:00:01.5Z").unwrap(),
+            SporeValue::Timestamp(
+                std::time::UNIX_EPOCH + std::time::Duration::from_millis(86_401_500)
+            )
+        );
+        assert!(parse("1u8x").is_err());
+        assert_eq!(parse("-inf").unwrap(), SporeValue::Float(f64::NEG_INFINITY));
+        assert!(matches!(parse("NaN").unwrap(), SporeValue::Float(f) if f.is_nan()));
+        assert_eq!(parse("true").unwrap(), SporeValue::Bool(true));
+        assert_eq!(parse("()").unwrap(), SporeValue::Unit);
+        assert_eq!(
+            parse(r#""say \"hi\"\n\t\\ \u{1f344}""#).unwrap(),
+            string("say \"hi\"\n\t\\ \u{1f344}")
+        );
+        assert_eq!(parse(r"'\''").unwrap(), SporeValue::Char('\''));
+        assert_eq!(parse("'a'").unwrap(), SporeValue::Char('a'));
+        assert_eq!(
+            parse("<redacted:1a2b>").unwrap(),
+            SporeValue::Marker("redacted:1a2b".to_string())
+        );
+    }
+
+    /// Tests parsing of lists, maps and tuples
+    #[test]
+    fn test_collections() {
+        assert_eq!(
+            parse("[[1, 2], []]").unwrap(),
+            SporeValue::List(vec![
+                SporeValue::List(vec![int(1), int(2)]),
+                SporeValue::List(vec![])
+            ])
+        );
+        assert_eq!(
+            parse(r#"{"a": 1, "b": [2]}"#).unwrap(),
+            SporeValue::Map(vec![
+                (string("a"), int(1)),
+                (string("b"), SporeValue::List(vec![int(2)]))
+            ])
+        );
+        assert_eq!(parse("{}").unwrap(), SporeValue::Map(vec![]));
+        assert_eq!(parse("(42,)").unwrap(), SporeValue::Tuple(vec![int(42)]));
+        assert_eq!(
+            parse(r#"(1, "a", 'b')"#).unwrap(),
+            SporeValue::Tuple(vec![int(1), string("a"), SporeValue::Char('b')])
+        );
+    }
+
+    /// Tests parsing of `Option`, `Result` and ranges
+    #[test]
+    fn test_option_result_range() {
+        assert_eq!(
+            parse("Some(None)").unwrap(),
+            SporeValue::Option(Some(Box::new(SporeValue::Option(None))))
+        );
+        assert_eq!(
+            parse("Ok(1)").unwrap(),
+            SporeValue::Result(Ok(Box::new(int(1))))
+        );
+        assert_eq!(
+            parse(r#"Err("error")"#).unwrap(),
+            SporeValue::Result(Err(Box::new(string("error"))))
+        );
+        assert_eq!(
+            parse("-5..5").unwrap(),
+            SporeValue::Range {
+                start: Some(Box::new(int(-5))),
+                end: Some(Box::new(int(5))),
+                inclusive: false,
+            }
+        );
+        assert_eq!(
+            parse("'a'..='z'").unwrap(),
+            SporeValue::Range {
+                start: Some(Box::new(SporeValue::Char('a'))),
+                end: Some(Box::new(SporeValue::Char('z'))),
+                inclusive: true,
+            }
+        );
+        assert_eq!(
+            parse("[1.., ..2, ..]").unwrap(),
+            SporeValue::List(vec![
+                SporeValue::Range {
+                    start: Some(Box::new(int(1))),
+                    end: None,
+                    inclusive: false,
+                },
+                SporeValue::Range {
+                    start: None,
+                    end: Some(Box::new(int(2))),
+                    inclusive: false,
+                },
+                SporeValue::Range {
+                    start: None,
+                    end: None,
+                    inclusive: false,
+                },
+            ])
+        );
+    }
+
+    /// Tests parsing of derived structs and enum variants
+    #[test]
+    fn test_structs_and_variants() {
+        assert_eq!(
+            parse("Unit").unwrap(),
+            SporeValue::UnitStruct("Unit".to_string())
+        );
+        assert_eq!(
+            parse(r#"Point { x: 1, r#type: "a" }"#).unwrap(),
+            SporeValue::Struct {
+                name: "Point".to_string(),
+                fields: vec![
+                    ("x".to_string(), int(1)),
+                    ("r#type".to_string(), string("a"))
+                ],
+            }
+        );
+        assert_eq!(
+            parse("Empty {  }").unwrap(),
+            SporeValue::Struct {
+                name: "Empty".to_string(),
+                fields: vec![],
+            }
+        );
+        assert_eq!(
+            parse("Pair(1, 2)").unwrap(),
+            SporeValue::TupleStruct {
+                name: "Pair".to_string(),
+                fields: vec![int(1), int(2)],
+            }
+        );
+        assert_eq!(
+            parse("Shape::Square").unwrap(),
+            SporeValue::Variant {
+                enum_name: "Shape".to_string(),
+                variant: Box::new(SporeValue::UnitStruct("Square".to_string())),
+            }
+        );
+        assert_eq!(
+            parse("Shape::Rect(1, 2)").unwrap(),
+            SporeValue::Variant {
+                enum_name: "Shape".to_string(),
+                variant: Box::new(SporeValue::TupleStruct {
+                    name: "Rect".to_string(),
+                    fields: vec![int(1), int(2)],
+                }),
+            }
+        );
+    }
+
+    /// Tests that whitespace and trailing commas are accepted
+    #[test]
+    fn test_whitespace_and_trailing_commas() {
+        let value = parse("Point {\n    x: [\n        1,\n        2,\n    ],\n}\n").unwrap();
+        assert_eq!(
+            value,
+            SporeValue::Struct {
+                name: "Point".to_string(),
+                fields: vec![("x".to_string(), SporeValue::List(vec![int(1), int(2)]))],
+            }
+        );
+    }
+
+    /// Tests that errors report the line and column of the offending input
+    #[test]
+    fn test_errors() {
+        let error = parse("Point {\n    x: 1\n    y: 2\n}").unwrap_err();
+        assert_eq!(error.message(), "expected `,` or `}`, found `y`");
+        assert_eq!((error.line(), error.column()), (3, 5));
+
+        let error = parse("(1)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a trailing `,` in a single-element tuple at line 1, column 2"
+        );
+
+        let error = parse(r#""abc"#).unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 5));
+
+        let error = parse(r#""\q""#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown escape sequence at line 1, column 2"
+        );
+
+        let error = parse("[1] 2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected end of input, found `2` at line 1, column 5"
+        );
+
+        let error = parse("[1.0000000001s]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid duration `1.0000000001s` at line 1, column 2"
+        );
+
+        let error = parse("2023-02-29T00:00:00Z").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid timestamp `2023-02-29T00:00:00Z` at line 1, column 1"
+        );
+
+        let error = parse("[<redacted\n]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `>`, found a line break at line 1, column 11"
+        );
+
+        let error = parse("99999999999999999999999999999999999999999").unwrap_err();
+        assert_eq!(error.column(), 1);
+
+        let error = parse(&"[".repeat(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!(error.message(), "nesting deeper than 256 levels");
+    }
+}
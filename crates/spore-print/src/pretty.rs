@@ -0,0 +1,275 @@
+use crate::{SporePrint, SporeValue};
+use std::fmt::{self, Write};
+
+/// Layout options for [`SporePrint::spore_print_pretty_with`].
+///
+/// A composite value (list, map, tuple, struct, variant with fields, `Some`, `Ok` or `Err`) is printed on one
+/// line when its compact form fits in the remaining `width`, and is otherwise broken into one line per item,
+/// indented by `indent` spaces, with a trailing comma after each item. The result parses back to the same
+/// value with [`crate::parse::parse`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{PrettyOptions, SporePrint};
+///
+/// let value = vec![(1, "one"), (2, "two")];
+/// assert_eq!(value.spore_print_pretty(), r#"[(1, "one"), (2, "two")]"#);
+///
+/// let options = PrettyOptions { indent: 2, width: 16 };
+/// assert_eq!(
+///     value.spore_print_pretty_with(&options),
+///     "[\n  (1, \"one\"),\n  (2, \"two\"),\n]"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Number of spaces per nesting level.
+    pub indent: usize,
+    /// Maximum line width, in chars, that a value printed on one line may reach.
+    pub width: usize,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        PrettyOptions {
+            indent: 4,
+            width: 80,
+        }
+    }
+}
+
+/// Renders `value` with the layout described by `options`.
+pub(crate) fn to_pretty_string(value: &SporeValue, options: &PrettyOptions) -> String {
+    let mut out = String::new();
+    PrettyWriter {
+        out: &mut out,
+        options,
+        column: 0,
+    }
+    .write_value(value, 0, 0)
+    .expect("writing a spore print to a String cannot fail");
+    out
+}
+
+/// An item in a broken composite: a positional value, a named field or a map entry.
+enum Item<'a> {
+    Value(&'a SporeValue),
+    Field(&'a str, &'a SporeValue),
+    Entry(&'a SporeValue, &'a SporeValue),
+}
+
+/// A writer that tracks the current column so that line widths can be respected.
+struct PrettyWriter<'a> {
+    out: &'a mut dyn Write,
+    options: &'a PrettyOptions,
+    column: usize,
+}
+
+impl Write for PrettyWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.rfind('\n') {
+            Some(newline) => self.column = s[newline + 1..].chars().count(),
+            None => self.column += s.chars().count(),
+        }
+        self.out.write_str(s)
+    }
+}
+
+impl PrettyWriter<'_> {
+    /// Writes `value` at nesting `level`, leaving room for `suffix` chars after it on the same line.
+    fn write_value(&mut self, value: &SporeValue, level: usize, suffix: usize) -> fmt::Result {
+        let budget = self.options.width.saturating_sub(self.column + suffix);
+        if fits(value, budget) {
+            return value.spore_print_to(self);
+        }
+
+        match value {
+            SporeValue::List(items) if !items.is_empty() => {
+                self.write_block("[", items.iter().map(Item::Value), "]", level)
+            }
+            SporeValue::Map(entries) if !entries.is_empty() => self.write_block(
+                "{",
+                entries.iter().map(|(key, value)| Item::Entry(key, value)),
+                "}",
+                level,
+            ),
+            SporeValue::Tuple(items) => {
+                self.write_block("(", items.iter().map(Item::Value), ")", level)
+            }
+            SporeValue::Option(Some(value)) => {
+                self.write_block("Some(", [Item::Value(value)], ")", level)
+            }
+            SporeValue::Result(Ok(value)) => {
+                self.write_block("Ok(", [Item::Value(value)], ")", level)
+            }
+            SporeValue::Result(Err(err)) => {
+                self.write_block("Err(", [Item::Value(err)], ")", level)
+            }
+            SporeValue::Struct { name, fields } if !fields.is_empty() => {
+                write!(self, "{} ", name)?;
+                self.write_block(
+                    "{",
+                    fields
+                        .iter()
+                        .map(|(field, value)| Item::Field(field, value)),
+                    "}",
+                    level,
+                )
+            }
+            SporeValue::TupleStruct { name, fields } if !fields.is_empty() => {
+                self.write_str(name)?;
+                self.write_block("(", fields.iter().map(Item::Value), ")", level)
+            }
+            SporeValue::Variant { enum_name, variant } => {
+                write!(self, "{}::", enum_name)?;
+                self.write_value(variant, level, suffix)
+            }
+            SporeValue::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let operator = if *inclusive { "..=" } else { ".." };
+                if let Some(start) = start {
+                    self.write_value(start, level, operator.len())?;
+                }
+                self.write_str(operator)?;
+                match end {
+                    Some(end) => self.write_value(end, level, suffix),
+                    None => Ok(()),
+                }
+            }
+            _ => value.spore_print_to(self),
+        }
+    }
+
+    /// Writes `items` one per line between `open` and `close`, indented one level deeper than `level`.
+    fn write_block<'v>(
+        &mut self,
+        open: &str,
+        items: impl IntoIterator<Item = Item<'v>>,
+        close: &str,
+        level: usize,
+    ) -> fmt::Result {
+        let indent = self.options.indent * (level + 1);
+        self.write_str(open)?;
+        for item in items {
+            write!(self, "\n{:indent$}", "", indent = indent)?;
+            match item {
+                Item::Value(value) => self.write_value(value, level + 1, 1)?,
+                Item::Field(field, value) => {
+                    write!(self, "{}: ", field)?;
+                    self.write_value(value, level + 1, 1)?;
+                }
+                Item::Entry(key, value) => {
+                    self.write_value(key, level + 1, 2)?;
+                    self.write_str(": ")?;
+                    self.write_value(value, level + 1, 1)?;
+                }
+            }
+            self.write_char(',')?;
+        }
+        write!(
+            self,
+            "\n{:indent$}{}",
+            "",
+            close,
+            indent = self.options.indent * level
+        )
+    }
+}
+
+/// Returns whether the compact form of `value` fits on one line within `budget` chars.
+fn fits(value: &SporeValue, budget: usize) -> bool {
+    /// A writer that fails as soon as more than `remaining` chars, or a line break, are written.
+    struct Budget {
+        remaining: usize,
+    }
+
+    impl Write for Budget {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let len = s.chars().count();
+            if len > self.remaining || s.contains('\n') {
+                return Err(fmt::Error);
+            }
+            self.remaining -= len;
+            Ok(())
+        }
+    }
+
+    value
+        .spore_print_to(&mut Budget { remaining: budget })
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse;
+
+    fn pretty(text: &str, width: usize) -> String {
+        let options = PrettyOptions { indent: 4, width };
+        parse(text).unwrap().spore_print_pretty_with(&options)
+    }
+
+    /// Tests that values fitting in the width stay on one line
+    #[test]
+    fn test_short_values_stay_flat() {
+        assert_eq!(pretty("[1, 2, 3]", 9), "[1, 2, 3]");
+        assert_eq!(pretty("Point { x: 1, y: 2 }", 80), "Point { x: 1, y: 2 }");
+        assert_eq!(pretty("[]", 0), "[]");
+        assert_eq!(pretty(r#""a long string""#, 0), r#""a long string""#);
+    }
+
+    /// Tests that composites are broken across lines, keeping short children on one line
+    #[test]
+    fn test_width_aware_layout() {
+        assert_eq!(pretty("[1, 2, 3]", 8), "[\n    1,\n    2,\n    3,\n]");
+        assert_eq!(
+            pretty(
+                r#"Config { name: "spore", ports: [80, 443], limits: {"cpu": 2, "memory": 512} }"#,
+                40
+            ),
+            r#"Config {
+    name: "spore",
+    ports: [80, 443],
+    limits: {"cpu": 2, "memory": 512},
+}"#
+        );
+        assert_eq!(
+            pretty(
+                r#"Shape::Polygon { points: [Point(0, 0), Point(10, 0), Point(10, 10)], closed: true }"#,
+                30
+            ),
+            r#"Shape::Polygon {
+    points: [
+        Point(0, 0),
+        Point(10, 0),
+        Point(10, 10),
+    ],
+    closed: true,
+}"#
+        );
+        assert_eq!(
+            pretty(r#"Some(("first", "second"))"#, 12),
+            r#"Some(
+    (
+        "first",
+        "second",
+    ),
+)"#
+        );
+        assert_eq!(pretty("(12345,)", 6), "(\n    12345,\n)");
+    }
+
+    /// Tests that pretty output parses back to the value it was printed from
+    #[test]
+    fn test_pretty_output_parses() {
+        let text = r#"{"a": Ok([1..=2, 3..4]), "b": Err(Unit)}"#;
+        for width in 0..=text.len() {
+            let value = parse(text).unwrap();
+            assert_eq!(parse(&pretty(text, width)).unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{SporeParseError, SporePrint, SporeValue};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// A wrapper that prints a marker in place of the wrapped value.
+///
+/// [`Redacted::new`] prints `<redacted>`. [`Redacted::hashed`] prints `<redacted:xxxxxxxx>`, where the eight hex
+/// digits are the start of the [`spore_fingerprint`](SporePrint::spore_fingerprint) of the value, so equal
+/// secrets can be correlated across prints without being shown. The digest is short and unsalted: it does not
+/// protect values that are easy to guess, such as PINs.
+///
+/// `#[spore(redact)]` and `#[spore(redact = "hash")]` apply the same markers to fields of derived types.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{Redacted, SporePrint};
+///
+/// let login = ("ada", Redacted::new("hunter2"));
+/// assert_eq!(login.spore_print(), r#"("ada", <redacted>)"#);
+/// assert_eq!(*login.1, "hunter2");
+///
+/// let token = Redacted::hashed("token-1234");
+/// assert_eq!(token.spore_print(), "<redacted:e5970fc4>");
+/// assert_eq!(token.spore_print(), Redacted::hashed("token-1234").spore_print());
+/// ```
+#[derive(Clone)]
+pub struct Redacted<T> {
+    value: T,
+    digest: Option<String>,
+}
+
+impl<T> Redacted<T> {
+    /// Wraps `value`, printing it as `<redacted>`.
+    pub fn new(value: T) -> Self {
+        Redacted {
+            value,
+            digest: None,
+        }
+    }
+
+    /// Wraps `value`, printing it as `<redacted:xxxxxxxx>` with a digest of its spore print.
+    pub fn hashed(value: T) -> Self
+    where
+        T: SporePrint,
+    {
+        let digest = value.spore_fingerprint()[..4]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Redacted {
+            value,
+            digest: Some(digest),
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the text of the marker, without the angle brackets.
+    fn marker(&self) -> String {
+        match &self.digest {
+            Some(digest) => format!("redacted:{}", digest),
+            None => "redacted".to_string(),
+        }
+    }
+}
+
+impl<T> Deref for Redacted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// Comparisons and hashing only look at the wrapped value, not at how it is printed.
+impl<T: PartialEq> PartialEq for Redacted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Redacted<T> {}
+
+impl<T: Hash> Hash for Redacted<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+// `Debug` is redacted too, so that wrapping a value is enough to keep it out of logs.
+impl<T> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.marker())
+    }
+}
+
+impl<T> SporePrint for Redacted<T> {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "<{}>", self.marker())
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Marker(self.marker())
+    }
+}
+
+/// Checks that a redacted field was printed as a redaction marker, for `#[derive(SporeParse)]`.
+#[doc(hidden)]
+pub fn expect_redacted(value: SporeValue) -> Result<(), SporeParseError> {
+    match value {
+        SporeValue::Marker(text) if text == "redacted" || text.starts_with("redacted:") => Ok(()),
+        other => Err(SporeParseError::mismatch("`<redacted>`", &other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the markers printed by `Redacted`
+    #[test]
+    fn test_redacted() {
+        let secret = Redacted::new(vec![1, 2, 3]);
+        assert_eq!(secret.spore_print(), "<redacted>");
+        assert_eq!(format!("{:?}", secret), "<redacted>");
+        assert_eq!(secret.len(), 3);
+        assert_eq!(
+            secret.to_spore_value(),
+            SporeValue::Marker("redacted".to_string())
+        );
+
+        let a = Redacted::hashed("secret");
+        let b = Redacted::hashed("secret".to_string());
+        let c = Redacted::hashed("Secret");
+        assert_eq!(a.spore_print(), b.spore_print());
+        assert_ne!(a.spore_print(), c.spore_print());
+        assert_eq!(a.spore_print().len(), "<redacted:>".len() + 8);
+        assert_eq!(
+            crate::parse::parse(&a.spore_print()).unwrap(),
+            a.to_spore_value()
+        );
+        assert_eq!(a, Redacted::new("secret"));
+        assert_eq!(a.into_inner(), "secret");
+    }
+
+    /// Tests that only redaction markers are accepted in place of redacted fields
+    #[test]
+    fn test_expect_redacted() {
+        assert!(expect_redacted(SporeValue::Marker("redacted".to_string())).is_ok());
+        assert!(expect_redacted(SporeValue::Marker("redacted:0a1b2c3d".to_string())).is_ok());
+        assert_eq!(
+            expect_redacted(SporeValue::Str("hunter2".to_string()))
+                .unwrap_err()
+                .to_string(),
+            "expected `<redacted>`, found a string"
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use crate::{parse, SporePrint, SporeValue};
+
+/// Rules replacing parts of a value with markers before it is printed, for values that change on every run.
+///
+/// Redactions work on the [`SporeValue`] of a value, so they apply alike to built-in types and to derived
+/// ones. A matching part is replaced, whatever it holds, with the marker `<placeholder>`, and the parts inside
+/// it are not visited. Rules are tried in the order they were added.
+///
+/// Paths are selected with the syntax of the paths in [`assert_spore_eq!`](crate::assert_spore_eq) failures:
+///
+/// - `.name` selects a struct field, and `.0` a tuple or tuple struct item. `.start` and `.end` select the
+///   bounds of a range.
+/// - `[3]` selects a list item, and `["key"]` the value of a map entry, the key being written as it prints.
+/// - `.*` selects any field or tuple item, and `[*]` any list item or map value.
+///
+/// The payloads of `Some`, `Ok`, `Err` and enum variants add no segment: `.id` selects the `id` field of a
+/// value of type `Option<Struct>` as well as of a `Struct`. The empty path selects the whole value.
+///
+/// Pass redactions to [`assert_spore_snapshot!`](crate::assert_spore_snapshot) with `redact = ...`, or apply
+/// them directly with [`Redactions::apply`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{Redactions, SporePrint, SporeValue};
+///
+/// let redactions = Redactions::new()
+///     .path(".1[*].0", "id")
+///     .matching("float", |value| matches!(value, SporeValue::Float(_)));
+///
+/// let value = ("batch", vec![(9127, "a", 0.53), (4410, "b", 0.21)]);
+/// assert_eq!(
+///     redactions.apply(&value).spore_print(),
+///     r#"("batch", [(<id>, "a", <float>), (<id>, "b", <float>)])"#
+/// );
+/// ```
+#[derive(Default)]
+pub struct Redactions {
+    rules: Vec<(Rule, String)>,
+}
+
+/// What a redaction replaces.
+enum Rule {
+    /// The parts at the paths matching the selector.
+    Path(Vec<Selector>),
+    /// Struct-like values printed with the given type name.
+    TypeName(String),
+    /// The parts for which the predicate returns true.
+    Matching(Box<dyn Fn(&SporeValue) -> bool>),
+}
+
+/// A segment of a path selector.
+#[derive(Debug, PartialEq)]
+enum Selector {
+    /// `.name`
+    Field(String),
+    /// `.*`
+    AnyField,
+    /// `[3]`
+    Index(usize),
+    /// `["key"]`, with the key as it prints.
+    Key(String),
+    /// `[*]`
+    AnyItem,
+}
+
+/// A segment of the path of a part of a value.
+enum Segment {
+    Field(String),
+    Index(usize),
+    /// A map key, as it prints.
+    Key(String),
+}
+
+impl Selector {
+    fn matches(&self, segment: &Segment) -> bool {
+        match (self, segment) {
+            (Selector::Field(name), Segment::Field(field)) => name == field,
+            (Selector::AnyField, Segment::Field(_)) => true,
+            (Selector::Index(index), Segment::Index(other)) => index == other,
+            (Selector::Key(key), Segment::Key(other)) => key == other,
+            (Selector::AnyItem, Segment::Index(_) | Segment::Key(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Redactions {
+    /// Creates an empty set of redactions.
+    pub fn new() -> Self {
+        Redactions::default()
+    }
+
+    /// Replaces the parts at the paths matching `selector` with `<placeholder>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `selector` is not a valid path selector, or if `placeholder` contains `<`, `>` or a line break.
+    pub fn path(self, selector: &str, placeholder: &str) -> Self {
+        let selectors = parse_selector(selector).unwrap_or_else(|message| {
+            panic!("invalid redaction selector `{}`: {}", selector, message)
+        });
+        self.with_rule(Rule::Path(selectors), placeholder)
+    }
+
+    /// Replaces the structs, tuple structs, unit structs and enum variants printed with the type name `name`
+    /// with `<placeholder>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` contains `<`, `>` or a line break.
+    pub fn type_name(self, name: &str, placeholder: &str) -> Self {
+        self.with_rule(Rule::TypeName(name.to_string()), placeholder)
+    }
+
+    /// Replaces the parts for which `predicate` returns true with `<placeholder>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` contains `<`, `>` or a line break.
+    pub fn matching(
+        self,
+        placeholder: &str,
+        predicate: impl Fn(&SporeValue) -> bool + 'static,
+    ) -> Self {
+        self.with_rule(Rule::Matching(Box::new(predicate)), placeholder)
+    }
+
+    /// Returns the value of `value` with the redactions applied.
+    pub fn apply<T: SporePrint + ?Sized>(&self, value: &T) -> SporeValue {
+        let mut value = value.to_spore_value();
+        self.redact(&mut value, &mut Vec::new());
+        value
+    }
+
+    fn with_rule(mut self, rule: Rule, placeholder: &str) -> Self {
+        assert!(
+            !placeholder.contains(['<', '>', '\n', '\r']),
+            "invalid redaction placeholder `{}`: it must not contain `<`, `>` or a line break",
+            placeholder
+        );
+        self.rules.push((rule, placeholder.to_string()));
+        self
+    }
+
+    /// Returns the placeholder of the first rule matching `value`, found at `path`.
+    fn placeholder(&self, value: &SporeValue, path: &[Segment]) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(rule, _)| match rule {
+                Rule::Path(selectors) => {
+                    selectors.len() == path.len()
+                        && selectors
+                            .iter()
+                            .zip(path)
+                            .all(|(selector, segment)| selector.matches(segment))
+                }
+                Rule::TypeName(name) => type_name(value) == Some(name),
+                Rule::Matching(predicate) => predicate(value),
+            })
+            .map(|(_, placeholder)| placeholder.as_str())
+    }
+
+    /// Applies the redactions to `value`, found at `path`.
+    fn redact(&self, value: &mut SporeValue, path: &mut Vec<Segment>) {
+        match self.placeholder(value, path) {
+            Some(placeholder) => *value = SporeValue::Marker(placeholder.to_string()),
+            None => self.redact_parts(value, path),
+        }
+    }
+
+    /// Applies the redactions to the parts of `value`, found at `path`.
+    fn redact_parts(&self, value: &mut SporeValue, path: &mut Vec<Segment>) {
+        let mut redact_at = |segment: Segment, value: &mut SporeValue| {
+            path.push(segment);
+            self.redact(value, path);
+            path.pop();
+        };
+        match value {
+            SporeValue::List(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    redact_at(Segment::Index(index), item);
+                }
+            }
+            SporeValue::Tuple(items) | SporeValue::TupleStruct { fields: items, .. } => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    redact_at(Segment::Field(index.to_string()), item);
+                }
+            }
+            SporeValue::Struct { fields, .. } => {
+                for (name, field) in fields {
+                    redact_at(Segment::Field(name.clone()), field);
+                }
+            }
+            SporeValue::Map(entries) => {
+                for (key, value) in entries {
+                    redact_at(Segment::Key(key.spore_print()), value);
+                }
+            }
+            SporeValue::Range { start, end, .. } => {
+                for (name, bound) in [("start", start), ("end", end)] {
+                    if let Some(bound) = bound {
+                        redact_at(Segment::Field(name.to_string()), bound);
+                    }
+                }
+            }
+            // Payloads share the path of their parent.
+            SporeValue::Option(Some(inner)) | SporeValue::Result(Ok(inner) | Err(inner)) => {
+                self.redact(inner, path)
+            }
+            // The payload of a variant is named after the variant rather than a type, and is only redacted
+            // along with the whole variant.
+            SporeValue::Variant { variant, .. } => self.redact_parts(variant, path),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the type name printed for a struct-like value or enum variant.
+fn type_name(value: &SporeValue) -> Option<&String> {
+    match value {
+        SporeValue::UnitStruct(name)
+        | SporeValue::Struct { name, .. }
+        | SporeValue::TupleStruct { name, .. } => Some(name),
+        SporeValue::Variant { enum_name, .. } => Some(enum_name),
+        _ => None,
+    }
+}
+
+/// Parses a path selector such as `.items[*].id`.
+fn parse_selector(text: &str) -> Result<Vec<Selector>, String> {
+    let mut selectors = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '.' => {
+                let end = rest[1..].find(['.', '[']).map_or(rest.len(), |end| end + 1);
+                let name = &rest[1..end];
+                let selector = match name {
+                    "*" => Selector::AnyField,
+                    _ if !name.is_empty()
+                        && name.chars().all(|c| c == '_' || c.is_alphanumeric()) =>
+                    {
+                        Selector::Field(name.to_string())
+                    }
+                    _ => return Err(format!("`{}` is not a field name", name)),
+                };
+                selectors.push(selector);
+                rest = &rest[end..];
+            }
+            '[' => {
+                let end =
+                    closing_bracket(rest).ok_or_else(|| "`[` is not closed by `]`".to_string())?;
+                let inner = rest[1..end].trim();
+                let selector = if inner == "*" {
+                    Selector::AnyItem
+                } else if let Ok(index) = inner.parse() {
+                    Selector::Index(index)
+                } else {
+                    let key = parse::parse(inner)
+                        .map_err(|_| format!("`{}` is not an index, `*` or a map key", inner))?;
+                    Selector::Key(key.spore_print())
+                };
+                selectors.push(selector);
+                rest = &rest[end + 1..];
+            }
+            _ => return Err(format!("expected `.` or `[`, found `{}`", c)),
+        }
+    }
+    Ok(selectors)
+}
+
+/// Returns the position of the `]` closing the `[` that `text` starts with, skipping quoted strings and chars.
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' | '(' | '{' => depth += 1,
+                ']' | ')' | '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return (c == ']').then_some(index);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Returns the print of the value of `text` with `redactions` applied.
+    fn redact(redactions: &Redactions, text: &str) -> String {
+        redactions.apply(&parse::parse(text).unwrap()).spore_print()
+    }
+
+    /// Tests the parsing of path selectors
+    #[test]
+    fn test_parse_selector() {
+        assert_eq!(
+            parse_selector(r#".items[*].0["a]"][3].*"#).unwrap(),
+            vec![
+                Selector::Field("items".to_string()),
+                Selector::AnyItem,
+                Selector::Field("0".to_string()),
+                Selector::Key(r#""a]""#.to_string()),
+                Selector::Index(3),
+                Selector::AnyField,
+            ]
+        );
+        assert_eq!(parse_selector("").unwrap(), vec![]);
+        assert_eq!(
+            parse_selector(".items[*").unwrap_err(),
+            "`[` is not closed by `]`"
+        );
+        assert_eq!(
+            parse_selector("items").unwrap_err(),
+            "expected `.` or `[`, found `i`"
+        );
+        assert_eq!(
+            parse_selector(".a..b").unwrap_err(),
+            "`` is not a field name"
+        );
+        assert_eq!(
+            parse_selector("[x y]").unwrap_err(),
+            "`x y` is not an index, `*` or a map key"
+        );
+    }
+
+    /// Tests that path selectors replace exactly the selected parts
+    #[test]
+    fn test_path_redactions() {
+        let redactions = Redactions::new()
+            .path(".created_at", "timestamp")
+            .path(".items[*].id", "id")
+            .path(".tags[\"run\"]", "run");
+        assert_eq!(
+            redact(
+                &redactions,
+                r#"Some(Order { created_at: 1700000000, items: [Item { id: 3, name: "a" }, Item { id: 9, name: "b" }], tags: {"env": "ci", "run": 42} })"#
+            ),
+            r#"Some(Order { created_at: <timestamp>, items: [Item { id: <id>, name: "a" }, Item { id: <id>, name: "b" }], tags: {"env": "ci", "run": <run>} })"#
+        );
+        assert_eq!(
+            redact(&redactions, "Event::Created { created_at: 5, id: 1 }"),
+            "Event::Created { created_at: <timestamp>, id: 1 }"
+        );
+        assert_eq!(redact(&redactions, "[Item { id: 3 }]"), "[Item { id: 3 }]");
+        assert_eq!(
+            redact(&Redactions::new().path("", "all"), "(1, 2)"),
+            "<all>"
+        );
+        assert_eq!(
+            redact(&Redactions::new().path(".*", "x"), "(Point(1, 2), 3..4)"),
+            "(<x>, <x>)"
+        );
+        assert_eq!(
+            redact(
+                &Redactions::new().path(".0.1", "x").path(".1.end", "y"),
+                "(Point(1, 2), 3..4)"
+            ),
+            "(Point(1, <x>), 3..<y>)"
+        );
+    }
+
+    /// Tests type name and predicate rules over built-in values
+    #[test]
+    fn test_type_and_predicate_redactions() {
+        let redactions = Redactions::new()
+            .type_name("Uuid", "uuid")
+            .type_name("Instant", "instant")
+            .matching(
+                "address",
+                |value| matches!(value, SporeValue::Str(text) if text.starts_with("0x")),
+            );
+        let value: HashMap<&str, (&str, u8)> = [("ptr", ("0x7ffd", 1))].into_iter().collect();
+        assert_eq!(
+            redact(&redactions, &value.spore_print()),
+            r#"{"ptr": (<address>, 1)}"#
+        );
+        assert_eq!(
+            redact(
+                &redactions,
+                r#"[Uuid("67e5"), Instant, Kind::Uuid, Clock::Tick(Instant)]"#
+            ),
+            "[<uuid>, <instant>, Kind::Uuid, Clock::Tick(<instant>)]"
+        );
+    }
+
+    /// Tests that invalid placeholders are rejected
+    #[test]
+    #[should_panic(expected = "invalid redaction placeholder `a>b`")]
+    fn test_invalid_placeholder() {
+        let _ = Redactions::new().type_name("Uuid", "a>b");
+    }
+
+    /// Tests that placeholders that would not parse back as a marker are rejected
+    #[test]
+    #[should_panic(expected = "invalid redaction placeholder `a<b`")]
+    fn test_placeholder_with_opening_bracket() {
+        let _ = Redactions::new().path(".id", "a<b");
+    }
+}
@@ -0,0 +1,497 @@
+use crate::assert::write_differences;
+use crate::{parse, Redactions, SporePrint, FORMAT_VERSION};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Asserts that the pretty spore print of a value matches the snapshot stored for it.
+///
+/// Snapshots are stored in a `snapshots` directory next to the source file of the test, in
+/// `<file>__<name>.snap`, where `<name>` is the name given as first argument or, by default, the path of the
+/// test function within its crate with `::` replaced by `__`, such as `tests__test_total` for a `test_total`
+/// function in a `tests` module. Later unnamed snapshots of the same function in the same test get `-2`, `-3`,
+/// ... appended. Snapshots taken by a helper shared between tests must be named, since unnamed ones would be
+/// named after the helper in every test.
+///
+/// A snapshot file holds a short header followed by the
+/// [`spore_print_pretty`](crate::SporePrint::spore_print_pretty) of the value:
+///
+/// ```text
+/// ---
+/// source: tests/orders.rs
+/// expression: order
+/// format_version: 1
+/// ---
+/// Order {
+///     id: 7,
+///     ...
+/// }
+/// ```
+///
+/// Only the text after the header is compared. When it differs, the assertion fails with the paths where the
+/// values differ and the new snapshot is written next to the old one as `<file>__<name>.snap.new`, in the same
+/// format, for review: accepting it means renaming it over the `.snap` file.
+///
+/// The `SPORE_UPDATE_SNAPSHOTS` environment variable controls which files are written:
+///
+/// - `new`, the default: a missing snapshot is written and the assertion passes, and a mismatch writes a
+///   `.snap.new` file.
+/// - `always`: snapshots are written whether they match or not, and the assertion passes. Use it to accept
+///   every change after reviewing it.
+/// - `no`, the default when the `CI` environment variable is set: nothing is written, and a missing snapshot
+///   fails the assertion.
+///
+/// Parts of the value that change on every run, such as timestamps or generated ids, can be replaced with
+/// markers before it is printed by passing [`Redactions`](crate::Redactions) as `redact = ...` after the value.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spore_print::{assert_spore_snapshot, Redactions};
+/// # use spore_print::SporePrint;
+/// # struct Session;
+/// # impl Session { fn new(_: &str) -> Self { Session } }
+/// # impl SporePrint for Session { fn spore_print(&self) -> String { "Session".to_string() } }
+///
+/// #[test]
+/// fn test_default_settings() {
+///     let settings = vec![("retries", 3), ("timeout", 30)];
+///     assert_spore_snapshot!(settings);
+///     assert_spore_snapshot!("retry_setting", settings[0]);
+/// }
+///
+/// #[test]
+/// fn test_new_session() {
+///     let redactions = Redactions::new().path(".id", "id").path(".created_at", "timestamp");
+///     assert_spore_snapshot!(Session::new("ada"), redact = redactions);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_spore_snapshot {
+    ($name:literal, $value:expr, redact = $redactions:expr $(,)?) => {
+        $crate::__assert_spore_snapshot!(
+            ::std::option::Option::Some($name),
+            $value,
+            ::std::option::Option::Some(&$redactions)
+        )
+    };
+    ($name:literal, $value:expr $(,)?) => {
+        $crate::__assert_spore_snapshot!(
+            ::std::option::Option::Some($name),
+            $value,
+            ::std::option::Option::None
+        )
+    };
+    ($value:expr, redact = $redactions:expr $(,)?) => {
+        $crate::__assert_spore_snapshot!(
+            ::std::option::Option::None,
+            $value,
+            ::std::option::Option::Some(&$redactions)
+        )
+    };
+    ($value:expr $(,)?) => {
+        $crate::__assert_spore_snapshot!(
+            ::std::option::Option::None,
+            $value,
+            ::std::option::Option::None
+        )
+    };
+}
+
+/// Expands `assert_spore_snapshot!` once its arguments are normalized.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_spore_snapshot {
+    ($name:expr, $value:expr, $redactions:expr) => {
+        $crate::__private::assert_snapshot(
+            $crate::__private::SnapshotSite {
+                manifest_dir: ::std::env!("CARGO_MANIFEST_DIR"),
+                file: ::std::file!(),
+                function: $crate::__function_path!(),
+                name: $name,
+                expression: ::std::stringify!($value),
+            },
+            &$value,
+            $redactions,
+        )
+    };
+}
+
+/// Evaluates to the path of the enclosing function, such as `my_crate::tests::test_orders`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __function_path {
+    () => {{
+        fn f() {}
+        $crate::__private::enclosing_function(f)
+    }};
+}
+
+/// Returns the path of the function enclosing `f`, where `f` is a function item declared in it.
+#[doc(hidden)]
+pub fn enclosing_function<F>(_: F) -> &'static str {
+    let mut path = std::any::type_name::<F>().strip_suffix("::f").unwrap_or("");
+    // Snapshots taken in closures belong to the function defining them.
+    while let Some(outer) = path.strip_suffix("::{{closure}}") {
+        path = outer;
+    }
+    path
+}
+
+/// Where `assert_spore_snapshot!` was invoked.
+#[doc(hidden)]
+pub struct SnapshotSite {
+    /// The directory of the manifest of the crate invoking the macro.
+    pub manifest_dir: &'static str,
+    /// The source file of the invocation, as given by `file!()`.
+    pub file: &'static str,
+    /// The path of the function invoking the macro.
+    pub function: &'static str,
+    /// The explicit name of the snapshot, if any.
+    pub name: Option<&'static str>,
+    /// The source text of the value expression.
+    pub expression: &'static str,
+}
+
+/// Which snapshot files an assertion may write, from `SPORE_UPDATE_SNAPSHOTS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdateMode {
+    /// Write every snapshot.
+    Always,
+    /// Write missing snapshots, and `.snap.new` files for mismatches.
+    New,
+    /// Write nothing.
+    No,
+}
+
+impl UpdateMode {
+    fn from_env() -> Self {
+        match std::env::var("SPORE_UPDATE_SNAPSHOTS").as_deref() {
+            Ok("always") => UpdateMode::Always,
+            Ok("new") => UpdateMode::New,
+            Ok("no") => UpdateMode::No,
+            Ok(other) => panic!(
+                "invalid SPORE_UPDATE_SNAPSHOTS value `{}`, expected `always`, `new` or `no`",
+                other
+            ),
+            Err(_) if std::env::var_os("CI").is_some() => UpdateMode::No,
+            Err(_) => UpdateMode::New,
+        }
+    }
+}
+
+/// Compares the pretty print of `value`, with `redactions` applied, with its snapshot, for
+/// `assert_spore_snapshot!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_snapshot<T: SporePrint + ?Sized>(
+    site: SnapshotSite,
+    value: &T,
+    redactions: Option<&Redactions>,
+) {
+    let print = match redactions {
+        Some(redactions) => redactions.apply(value).spore_print_pretty(),
+        None => value.spore_print_pretty(),
+    };
+    let path = snapshot_path(&site);
+    let contents = snapshot_contents(&site, &print);
+    if let Err(message) = check_snapshot(&path, &contents, UpdateMode::from_env()) {
+        panic!("{}", message);
+    }
+}
+
+/// Returns the path of the snapshot file of the assertion at `site`.
+fn snapshot_path(site: &SnapshotSite) -> PathBuf {
+    thread_local! {
+        // Counts the unnamed snapshots taken so far by each function in the current test, to number the later
+        // ones. The test harness runs each test on its own thread, so the numbers follow the order of the calls
+        // within a test and do not depend on which tests ran before.
+        static COUNTS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+    }
+
+    let name = match site.name {
+        Some(name) => name.to_string(),
+        None => {
+            let function = site
+                .function
+                .split_once("::")
+                .map_or(site.function, |(_, path)| path)
+                .replace("::", "__");
+            let count = COUNTS.with(|counts| {
+                let mut counts = counts.borrow_mut();
+                let count = counts.entry(site.function).or_insert(0);
+                *count += 1;
+                *count
+            });
+            match count {
+                1 => function,
+                n => format!("{}-{}", function, n),
+            }
+        }
+    };
+
+    let source = site.source();
+    let stem = source.file_stem().unwrap_or_default().to_string_lossy();
+    source
+        .parent()
+        .unwrap_or(Path::new(site.manifest_dir))
+        .join("snapshots")
+        .join(format!("{}__{}.snap", stem, name))
+}
+
+impl SnapshotSite {
+    /// Returns the path of the source file.
+    ///
+    /// `file!()` is relative to the workspace root rather than to the crate, so the file is looked up from the
+    /// manifest directory upwards.
+    fn source(&self) -> PathBuf {
+        let manifest_dir = Path::new(self.manifest_dir);
+        manifest_dir
+            .ancestors()
+            .map(|dir| dir.join(self.file))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| manifest_dir.join(self.file))
+    }
+}
+
+/// Returns the contents of a snapshot file holding `print`.
+fn snapshot_contents(site: &SnapshotSite, print: &str) -> String {
+    let mut contents = String::from("---\n");
+    let source = site.source();
+    let source = source.strip_prefix(site.manifest_dir).unwrap_or(&source);
+    writeln!(
+        contents,
+        "source: {}",
+        source.to_string_lossy().replace('\\', "/")
+    )
+    .unwrap();
+    writeln!(
+        contents,
+        "expression: {}",
+        site.expression
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+    .unwrap();
+    writeln!(contents, "format_version: {}", FORMAT_VERSION).unwrap();
+    contents.push_str("---\n");
+    contents.push_str(print);
+    contents.push('\n');
+    contents
+}
+
+/// Returns the compared part of a snapshot file: the text after the header, without trailing line breaks.
+fn snapshot_body(contents: &str) -> &str {
+    let body = contents
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map_or(contents, |(_, body)| body);
+    body.trim_end_matches(['\n', '\r'])
+}
+
+/// Compares `contents` with the snapshot at `path`, writing the files allowed by `mode`.
+///
+/// Returns the failure message of the assertion, if it fails.
+fn check_snapshot(path: &Path, contents: &str, mode: UpdateMode) -> Result<(), String> {
+    let pending = path.with_extension("snap.new");
+    let old = match fs::read_to_string(path) {
+        Ok(old) => Some(old.replace("\r\n", "\n")),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => {
+            return Err(format!(
+                "cannot read snapshot {}: {}",
+                path.display(),
+                error
+            ))
+        }
+    };
+    let matches = old
+        .as_deref()
+        .is_some_and(|old| snapshot_body(old) == snapshot_body(contents));
+
+    if matches || mode == UpdateMode::Always || (old.is_none() && mode == UpdateMode::New) {
+        // Rewriting a matching snapshot would only refresh its header.
+        if !matches {
+            write_file(path, contents)?;
+        }
+        remove_file(&pending)?;
+        return Ok(());
+    }
+
+    let mut message = match &old {
+        Some(_) => format!("snapshot {} does not match", path.display()),
+        None => format!("snapshot {} does not exist", path.display()),
+    };
+    if mode == UpdateMode::New {
+        write_file(&pending, contents)?;
+        write!(
+            message,
+            ", the new snapshot was written to {} for review",
+            pending.display()
+        )
+        .unwrap();
+    }
+    if let Some(old) = &old {
+        match (
+            parse::parse(snapshot_body(old)),
+            parse::parse(snapshot_body(contents)),
+        ) {
+            (Ok(old), Ok(new)) => {
+                message.push('\n');
+                write_differences(&mut message, &old, &new, ["old", "new"]);
+            }
+            _ => write!(
+                message,
+                "\nold:\n{}\nnew:\n{}",
+                snapshot_body(old),
+                snapshot_body(contents)
+            )
+            .unwrap(),
+        }
+    }
+    message.push_str("\nrerun with SPORE_UPDATE_SNAPSHOTS=always to accept the new snapshot");
+    Err(message)
+}
+
+/// Writes `contents` to `path`, creating its directory.
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|error| format!("cannot create {}: {}", dir.display(), error))?;
+    }
+    fs::write(path, contents)
+        .map_err(|error| format!("cannot write snapshot {}: {}", path.display(), error))
+}
+
+/// Removes a stale pending snapshot at `path`, if any.
+fn remove_file(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("cannot remove {}: {}", path.display(), error))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a fresh directory for the snapshot files of a test.
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("spore-print-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Returns the site of a snapshot of the expression `value` taken in `function`.
+    fn site(function: &'static str, name: Option<&'static str>) -> SnapshotSite {
+        SnapshotSite {
+            manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            file: file!(),
+            function,
+            name,
+            expression: "value",
+        }
+    }
+
+    /// Tests the names and location of snapshot files
+    #[test]
+    fn test_snapshot_path() {
+        assert_eq!(
+            __function_path!(),
+            "spore_print::snapshot::tests::test_snapshot_path"
+        );
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/snapshots");
+        assert_eq!(
+            snapshot_path(&site(__function_path!(), Some("named"))),
+            dir.join("snapshot__named.snap")
+        );
+        assert_eq!(
+            snapshot_path(&site(__function_path!(), None)),
+            dir.join("snapshot__snapshot__tests__test_snapshot_path.snap")
+        );
+        assert_eq!(
+            snapshot_path(&site(__function_path!(), None)),
+            dir.join("snapshot__snapshot__tests__test_snapshot_path-2.snap")
+        );
+
+        // Functions with the same name in different modules get different snapshots.
+        assert_eq!(
+            snapshot_path(&site("orders::a::test_total", None)),
+            dir.join("snapshot__a__test_total.snap")
+        );
+        assert_eq!(
+            snapshot_path(&site("orders::b::test_total", None)),
+            dir.join("snapshot__b__test_total.snap")
+        );
+
+        // Numbering restarts in every test thread.
+        let helper = "orders::helpers::check";
+        assert_eq!(
+            snapshot_path(&site(helper, None)),
+            dir.join("snapshot__helpers__check.snap")
+        );
+        let other_test = std::thread::spawn(move || snapshot_path(&site(helper, None)));
+        assert_eq!(
+            other_test.join().unwrap(),
+            dir.join("snapshot__helpers__check.snap")
+        );
+        assert_eq!(
+            snapshot_path(&site(helper, None)),
+            dir.join("snapshot__helpers__check-2.snap")
+        );
+    }
+
+    /// Tests the format of snapshot files
+    #[test]
+    fn test_snapshot_contents() {
+        let contents = snapshot_contents(&site("f", None), "[\n    1,\n]");
+        assert_eq!(
+            contents,
+            "---\nsource: src/snapshot.rs\nexpression: value\nformat_version: 1\n---\n[\n    1,\n]\n"
+        );
+        assert_eq!(snapshot_body(&contents), "[\n    1,\n]");
+        assert_eq!(snapshot_body("---\nsource: a.rs\n---\n---\n"), "---");
+    }
+
+    /// Tests which files each update mode writes
+    #[test]
+    fn test_check_snapshot() {
+        let dir = temp_dir("check");
+        let path = dir.join("test__value.snap");
+        let pending = dir.join("test__value.snap.new");
+        let old = "---\nsource: a.rs\n---\n(1, [\"a\"])\n";
+        let new = "---\nsource: b.rs\n---\n(1, [\"b\"])\n";
+
+        let error = check_snapshot(&path, old, UpdateMode::No).unwrap_err();
+        assert!(error.ends_with(
+            "does not exist\nrerun with SPORE_UPDATE_SNAPSHOTS=always to accept the new snapshot"
+        ));
+        assert!(!path.exists());
+
+        check_snapshot(&path, old, UpdateMode::New).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+        check_snapshot(
+            &path,
+            "---\nsource: c.rs\n---\n(1, [\"a\"])",
+            UpdateMode::No,
+        )
+        .unwrap();
+
+        let error = check_snapshot(&path, new, UpdateMode::New).unwrap_err();
+        assert!(error.contains(
+            "for review\nthe spore prints differ at 1 place\n  .1[0]\n    old: \"a\"\n    new: \"b\"\n"
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+        assert_eq!(fs::read_to_string(&pending).unwrap(), new);
+
+        check_snapshot(&path, new, UpdateMode::Always).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), new);
+        assert!(!pending.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,963 @@
+use crate::parse::{parse, ParseError};
+use crate::{SporePrint, SporeValue};
+use std::borrow::Cow;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::{self, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+use std::ops::{
+    Bound, ControlFlow, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+use std::rc::Rc;
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize,
+};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::task::Poll;
+use std::time::{Duration, SystemTime};
+
+/// The `SporeParse` trait is the inverse of [`SporePrint`]: it reads a value back from its spore print.
+///
+/// It is implemented for the owned types `SporePrint` is implemented for, except those whose print does not hold
+/// their value: `Instant`, `Weak` pointers and [`Redacted`](crate::Redacted). Pointers such as `Box` and `Arc`
+/// and cells such as `Mutex` are read as a new pointer or cell holding the value, and a `Cow` as an owned value.
+/// It can be derived with `#[derive(SporeParse)]` from `spore-print-derive` for structs and enums whose fields
+/// are `SporeParse`.
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::{SporeParse, SporePrint};
+/// use std::collections::HashMap;
+///
+/// let map = HashMap::from([("a".to_string(), vec![Some(1)])]);
+/// let text = map.spore_print();
+/// assert_eq!(text, r#"{"a": [Some(1)]}"#);
+/// assert_eq!(HashMap::spore_parse(&text).unwrap(), map);
+///
+/// let error = <Vec<u8>>::spore_parse("[1, 300]").unwrap_err();
+/// assert_eq!(error.to_string(), "expected `u8`, found `300` at `[1]`");
+/// ```
+pub trait SporeParse: Sized {
+    /// Parses spore print text into `Self`.
+    fn spore_parse(s: &str) -> Result<Self, SporeParseError> {
+        Self::from_spore_value(parse(s)?)
+    }
+
+    /// Converts a parsed [`SporeValue`] into `Self`.
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError>;
+}
+
+/// An error produced when spore print text cannot be read back into a type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SporeParseError {
+    /// The text does not follow the spore print grammar.
+    Syntax(ParseError),
+    /// The text is well-formed, but a value at `path` does not fit the target type.
+    Mismatch {
+        /// The location of the value, such as `.items[3].name`, or empty for the root value.
+        path: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl SporeParseError {
+    /// Creates an error for a value that was `found` where `expected` was required.
+    pub fn mismatch(expected: impl Into<String>, found: &SporeValue) -> Self {
+        SporeParseError::Mismatch {
+            path: String::new(),
+            expected: expected.into(),
+            found: describe(found),
+        }
+    }
+
+    /// Prefixes the path of the error with the field `name`.
+    pub fn in_field(self, name: &str) -> Self {
+        self.prefix_path(&format!(".{}", name))
+    }
+
+    /// Prefixes the path of the error with the list or tuple position `index`.
+    pub fn at_index(self, index: usize) -> Self {
+        self.prefix_path(&format!("[{}]", index))
+    }
+
+    fn prefix_path(self, prefix: &str) -> Self {
+        match self {
+            SporeParseError::Mismatch {
+                path,
+                expected,
+                found,
+            } => SporeParseError::Mismatch {
+                path: format!("{}{}", prefix, path),
+                expected,
+                found,
+            },
+            syntax => syntax,
+        }
+    }
+}
+
+impl fmt::Display for SporeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SporeParseError::Syntax(error) => error.fmt(f),
+            SporeParseError::Mismatch {
+                path,
+                expected,
+                found,
+            } => {
+                write!(f, "expected {}, found {}", expected, found)?;
+                if !path.is_empty() {
+                    write!(f, " at `{}`", path)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for SporeParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SporeParseError::Syntax(error) => Some(error),
+            SporeParseError::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for SporeParseError {
+    fn from(error: ParseError) -> Self {
+        SporeParseError::Syntax(error)
+    }
+}
+
+// Helper function to describe a value in error messages: scalars by their spore print, composites by their kind
+fn describe(value: &SporeValue) -> String {
+    match value {
+        SporeValue::Unit
+        | SporeValue::Bool(_)
+        | SporeValue::Int(_)
+        | SporeValue::UInt(_)
+        | SporeValue::Float(_)
+        | SporeValue::Duration(_)
+        | SporeValue::Timestamp(_)
+        | SporeValue::Char(_) => format!("`{}`", value.spore_print()),
+        SporeValue::Str(_) => "a string".to_string(),
+        SporeValue::List(items) => format!("a list of {} items", items.len()),
+        SporeValue::Map(_) => "a map".to_string(),
+        SporeValue::Tuple(items) => format!("a tuple of {} items", items.len()),
+        SporeValue::Option(_) => "an option".to_string(),
+        SporeValue::Result(_) => "a result".to_string(),
+        SporeValue::Range { .. } => "a range".to_string(),
+        SporeValue::UnitStruct(name) => format!("unit struct `{}`", name),
+        SporeValue::Struct { name, .. } => format!("struct `{}`", name),
+        SporeValue::TupleStruct { name, .. } => format!("tuple struct `{}`", name),
+        SporeValue::Variant { enum_name, variant } => match &**variant {
+            SporeValue::UnitStruct(name)
+            | SporeValue::Struct { name, .. }
+            | SporeValue::TupleStruct { name, .. } => {
+                format!("variant `{}::{}`", enum_name, name)
+            }
+            _ => format!("a variant of `{}`", enum_name),
+        },
+        SporeValue::Marker(text) => format!("`<{}>`", text),
+        SporeValue::Raw(_) => "raw text".to_string(),
+    }
+}
+
+/// Takes the values of named `fields` in the order of `names`, rejecting unknown, duplicate and missing fields.
+#[doc(hidden)]
+pub fn take_fields<const N: usize>(
+    fields: Vec<(String, SporeValue)>,
+    names: [&str; N],
+) -> Result<[SporeValue; N], SporeParseError> {
+    let mut slots: [Option<SporeValue>; N] = std::array::from_fn(|_| None);
+    for (name, value) in fields {
+        let found = describe(&value);
+        let index = names
+            .iter()
+            .position(|expected| *expected == name)
+            .ok_or_else(|| SporeParseError::Mismatch {
+                path: format!(".{}", name),
+                expected: format!("one of the fields {}", names.join(", ")),
+                found: found.clone(),
+            })?;
+        if slots[index].replace(value).is_some() {
+            return Err(SporeParseError::Mismatch {
+                path: format!(".{}", name),
+                expected: "each field once".to_string(),
+                found: format!("a duplicate {}", found),
+            });
+        }
+    }
+    if let Some(index) = slots.iter().position(Option::is_none) {
+        return Err(SporeParseError::Mismatch {
+            path: format!(".{}", names[index]),
+            expected: "a value".to_string(),
+            found: "nothing".to_string(),
+        });
+    }
+    Ok(slots.map(Option::unwrap))
+}
+
+/// Takes exactly `N` positional `items`, as found in tuples and tuple structs.
+#[doc(hidden)]
+pub fn take_items<const N: usize>(
+    items: Vec<SporeValue>,
+) -> Result<[SporeValue; N], SporeParseError> {
+    let len = items.len();
+    items.try_into().map_err(|_| SporeParseError::Mismatch {
+        path: String::new(),
+        expected: format!("{} items", N),
+        found: format!("{} items", len),
+    })
+}
+
+impl SporeParse for SporeValue {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        Ok(value)
+    }
+}
+
+// Implement `SporeParse` for integers, which accept any integer literal in their range
+macro_rules! impl_spore_parse_for_integers {
+    ($($t:ty),*) => {
+        $(impl SporeParse for $t {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                let parsed = match value {
+                    SporeValue::Int(int) => <$t>::try_from(int).ok(),
+                    SporeValue::UInt(int) => <$t>::try_from(int).ok(),
+                    _ => None,
+                };
+                parsed.ok_or_else(|| SporeParseError::mismatch(concat!("`", stringify!($t), "`"), &value))
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_integers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Implement `SporeParse` for floats, which also accept integer literals as whole numbers
+macro_rules! impl_spore_parse_for_floats {
+    ($($t:ty),*) => {
+        $(impl SporeParse for $t {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                match value {
+                    SporeValue::Float(float) => Ok(float as $t),
+                    SporeValue::Int(int) => Ok(int as $t),
+                    SporeValue::UInt(int) => Ok(int as $t),
+                    other => Err(SporeParseError::mismatch(concat!("`", stringify!($t), "`"), &other)),
+                }
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_floats!(f32, f64);
+
+// Implement `SporeParse` for non-zero integers, which reject zero
+macro_rules! impl_spore_parse_for_non_zero {
+    ($($t:ty: $int:ty),*) => {
+        $(impl SporeParse for $t {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                let int = <$int>::from_spore_value(value)?;
+                <$t>::new(int).ok_or_else(|| {
+                    SporeParseError::mismatch(concat!("a non-zero `", stringify!($int), "`"), &SporeValue::UInt(0))
+                })
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_non_zero!(NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64,
+    NonZeroU128: u128, NonZeroUsize: usize, NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64,
+    NonZeroI128: i128, NonZeroIsize: isize);
+
+// Implement `SporeParse` for atomics, which read the value they are created with
+macro_rules! impl_spore_parse_for_atomics {
+    ($($t:ty: $value:ty),*) => {
+        $(impl SporeParse for $t {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                <$value>::from_spore_value(value).map(<$t>::new)
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_atomics!(AtomicBool: bool, AtomicU8: u8, AtomicU16: u16, AtomicU32: u32, AtomicU64: u64,
+    AtomicUsize: usize, AtomicI8: i8, AtomicI16: i16, AtomicI32: i32, AtomicI64: i64, AtomicIsize: isize);
+
+impl SporeParse for bool {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Bool(value) => Ok(value),
+            other => Err(SporeParseError::mismatch("`bool`", &other)),
+        }
+    }
+}
+
+impl SporeParse for char {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Char(value) => Ok(value),
+            other => Err(SporeParseError::mismatch("a char", &other)),
+        }
+    }
+}
+
+impl SporeParse for String {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Str(value) => Ok(value),
+            other => Err(SporeParseError::mismatch("a string", &other)),
+        }
+    }
+}
+
+impl SporeParse for Duration {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Duration(value) => Ok(value),
+            other => Err(SporeParseError::mismatch("a duration", &other)),
+        }
+    }
+}
+
+impl SporeParse for SystemTime {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Timestamp(value) => Ok(value),
+            other => Err(SporeParseError::mismatch("a timestamp", &other)),
+        }
+    }
+}
+
+impl SporeParse for () {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Unit => Ok(()),
+            other => Err(SporeParseError::mismatch("`()`", &other)),
+        }
+    }
+}
+
+impl<T> SporeParse for Option<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Option(Some(value)) => T::from_spore_value(*value).map(Some),
+            SporeValue::Option(None) => Ok(None),
+            other => Err(SporeParseError::mismatch("an option", &other)),
+        }
+    }
+}
+
+impl<T, E> SporeParse for Result<T, E>
+where
+    T: SporeParse,
+    E: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Result(Ok(value)) => T::from_spore_value(*value).map(Ok),
+            SporeValue::Result(Err(err)) => E::from_spore_value(*err).map(Err),
+            other => Err(SporeParseError::mismatch("a result", &other)),
+        }
+    }
+}
+
+// Helper function to convert the items of a list, reporting errors at the index of the offending item
+fn from_list<T, C>(value: SporeValue) -> Result<C, SporeParseError>
+where
+    T: SporeParse,
+    C: FromIterator<T>,
+{
+    match value {
+        SporeValue::List(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| T::from_spore_value(item).map_err(|e| e.at_index(index)))
+            .collect(),
+        other => Err(SporeParseError::mismatch("a list", &other)),
+    }
+}
+
+// Implement `SporeParse` for collections that print as a list
+macro_rules! impl_spore_parse_for_collections {
+    ($($t:ident<T> $(where T: $bound:ident)?),*) => {
+        $(impl<T> SporeParse for $t<T>
+        where
+            T: SporeParse $(+ $bound)?,
+        {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                from_list(value)
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_collections!(
+    Vec<T>,
+    VecDeque<T>,
+    LinkedList<T>,
+    BTreeSet<T> where T: Ord,
+    BinaryHeap<T> where T: Ord
+);
+
+impl<T, S> SporeParse for HashSet<T, S>
+where
+    T: SporeParse + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        from_list(value)
+    }
+}
+
+impl<T, const N: usize> SporeParse for [T; N]
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        let items: Vec<T> = from_list(value)?;
+        let len = items.len();
+        items.try_into().map_err(|_| SporeParseError::Mismatch {
+            path: String::new(),
+            expected: format!("a list of {} items", N),
+            found: format!("a list of {} items", len),
+        })
+    }
+}
+
+// Helper function to convert the entries of a map, reporting errors at the key of the offending entry
+fn from_map<K, V, C>(value: SporeValue) -> Result<C, SporeParseError>
+where
+    K: SporeParse,
+    V: SporeParse,
+    C: FromIterator<(K, V)>,
+{
+    match value {
+        SporeValue::Map(entries) => entries
+            .into_iter()
+            .map(|(key, value)| {
+                let path = format!("[{}]", key.spore_print());
+                let key = K::from_spore_value(key).map_err(|e| e.prefix_path(&path))?;
+                let value = V::from_spore_value(value).map_err(|e| e.prefix_path(&path))?;
+                Ok((key, value))
+            })
+            .collect(),
+        other => Err(SporeParseError::mismatch("a map", &other)),
+    }
+}
+
+impl<K, V, S> SporeParse for HashMap<K, V, S>
+where
+    K: SporeParse + Eq + Hash,
+    V: SporeParse,
+    S: BuildHasher + Default,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        from_map(value)
+    }
+}
+
+impl<K, V> SporeParse for BTreeMap<K, V>
+where
+    K: SporeParse + Ord,
+    V: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        from_map(value)
+    }
+}
+
+/// Macro to implement `SporeParse` for tuples of varying lengths, matching the `SporePrint` implementations.
+macro_rules! impl_spore_parse_for_tuples {
+    ($($T:ident),+) => {
+        impl<$($T: SporeParse),+> SporeParse for ($($T,)+) {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                match value {
+                    SporeValue::Tuple(items) => {
+                        #[allow(non_snake_case)]
+                        let [$($T),+] = take_items(items)?;
+                        let mut indices = 0..;
+                        Ok(($({
+                            let index = indices.next().unwrap_or_default();
+                            $T::from_spore_value($T).map_err(|e| e.at_index(index))?
+                        },)+))
+                    }
+                    other => Err(SporeParseError::mismatch("a tuple", &other)),
+                }
+            }
+        }
+    };
+}
+
+impl_spore_parse_for_tuples!(T1);
+impl_spore_parse_for_tuples!(T1, T2);
+impl_spore_parse_for_tuples!(T1, T2, T3);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_spore_parse_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+// Helper function to take both bounds of a range with the given inclusiveness
+fn range_bounds<T: SporeParse>(
+    value: SporeValue,
+    inclusive: bool,
+) -> Result<(T, T), SporeParseError> {
+    match value {
+        SporeValue::Range {
+            start: Some(start),
+            end: Some(end),
+            inclusive: found,
+        } if found == inclusive => Ok((
+            T::from_spore_value(*start).map_err(|e| e.in_field("start"))?,
+            T::from_spore_value(*end).map_err(|e| e.in_field("end"))?,
+        )),
+        other if inclusive => Err(SporeParseError::mismatch("a range `a..=b`", &other)),
+        other => Err(SporeParseError::mismatch("a range `a..b`", &other)),
+    }
+}
+
+impl<T> SporeParse for Range<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        let (start, end) = range_bounds(value, false)?;
+        Ok(start..end)
+    }
+}
+
+impl<T> SporeParse for RangeInclusive<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        let (start, end) = range_bounds(value, true)?;
+        Ok(start..=end)
+    }
+}
+
+// Helper function to convert one bound of a range, reporting errors at `start` or `end`
+fn range_bound<T: SporeParse>(bound: SporeValue, name: &str) -> Result<T, SporeParseError> {
+    T::from_spore_value(bound).map_err(|e| e.in_field(name))
+}
+
+impl<T> SporeParse for RangeFrom<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Range {
+                start: Some(start),
+                end: None,
+                inclusive: false,
+            } => Ok(range_bound(*start, "start")?..),
+            other => Err(SporeParseError::mismatch("a range `a..`", &other)),
+        }
+    }
+}
+
+impl<T> SporeParse for RangeTo<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Range {
+                start: None,
+                end: Some(end),
+                inclusive: false,
+            } => Ok(..range_bound(*end, "end")?),
+            other => Err(SporeParseError::mismatch("a range `..b`", &other)),
+        }
+    }
+}
+
+impl<T> SporeParse for RangeToInclusive<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Range {
+                start: None,
+                end: Some(end),
+                inclusive: true,
+            } => Ok(..=range_bound(*end, "end")?),
+            other => Err(SporeParseError::mismatch("a range `..=b`", &other)),
+        }
+    }
+}
+
+impl SporeParse for RangeFull {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::Range {
+                start: None,
+                end: None,
+                inclusive: false,
+            } => Ok(..),
+            other => Err(SporeParseError::mismatch("a range `..`", &other)),
+        }
+    }
+}
+
+// Implement `SporeParse` for types that print as the value they hold, by creating them around the parsed value
+macro_rules! impl_spore_parse_for_wrappers {
+    ($($t:ident => $new:expr),*) => {
+        $(impl<T> SporeParse for $t<T>
+        where
+            T: SporeParse,
+        {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                T::from_spore_value(value).map($new)
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_wrappers!(
+    Box => Box::new,
+    Rc => Rc::new,
+    Arc => Arc::new,
+    Cell => Cell::new,
+    RefCell => RefCell::new,
+    Mutex => Mutex::new,
+    RwLock => RwLock::new,
+    Wrapping => Wrapping,
+    Saturating => Saturating
+);
+
+// Implement `SporeParse` for pointers to strings and slices, which are converted from a `String` or `Vec<T>`
+macro_rules! impl_spore_parse_for_unsized_pointers {
+    ($($t:ident),*) => {
+        $(
+            impl SporeParse for $t<str> {
+                fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                    String::from_spore_value(value).map($t::from)
+                }
+            }
+
+            impl<T> SporeParse for $t<[T]>
+            where
+                T: SporeParse,
+            {
+                fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                    <Vec<T>>::from_spore_value(value).map($t::from)
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_parse_for_unsized_pointers!(Box, Rc, Arc);
+
+// Implement `SporeParse` for `Cow<'_, B>` as an owned value
+impl<B> SporeParse for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        B::Owned::from_spore_value(value).map(Cow::Owned)
+    }
+}
+
+// Implement `SporeParse` for cells initialized at most once, which are left unset for the marker `<uninit>`
+macro_rules! impl_spore_parse_for_once_cells {
+    ($($t:ident),*) => {
+        $(impl<T> SporeParse for $t<T>
+        where
+            T: SporeParse,
+        {
+            fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                match value {
+                    SporeValue::Marker(marker) if marker == "uninit" => Ok($t::new()),
+                    value => T::from_spore_value(value).map($t::from),
+                }
+            }
+        })*
+    };
+}
+
+impl_spore_parse_for_once_cells!(OnceCell, OnceLock);
+
+// Helper function to take a variant of the standard library enum `enum_name`, as its name and the fields it holds
+// if it is a tuple variant
+fn take_variant(
+    value: SporeValue,
+    enum_name: &str,
+) -> Result<(String, Option<Vec<SporeValue>>), SporeParseError> {
+    let expected = format!("a variant of `{}`", enum_name);
+    match value {
+        SporeValue::Variant {
+            enum_name: found,
+            variant,
+        } if found == enum_name => match *variant {
+            SporeValue::UnitStruct(name) => Ok((name, None)),
+            SporeValue::TupleStruct { name, fields } => Ok((name, Some(fields))),
+            other => Err(SporeParseError::mismatch(expected, &other)),
+        },
+        other => Err(SporeParseError::mismatch(expected, &other)),
+    }
+}
+
+// Helper function to convert the single field of a tuple variant or struct, reporting errors at `.0` like derived
+// types do
+fn variant_field<T: SporeParse>(fields: Vec<SporeValue>) -> Result<T, SporeParseError> {
+    let [field] = take_items(fields)?;
+    T::from_spore_value(field).map_err(|e| e.in_field("0"))
+}
+
+// Matches the fields of a tuple variant holding `$value`, or the absence of fields of a unit variant
+macro_rules! variant_fields {
+    () => {
+        None
+    };
+    ($value:ident) => {
+        Some($value)
+    };
+}
+
+// Implement `SporeParse` for enums of the standard library that print with their enum name, as in
+// `Bound::Included(1)`, matching the `SporePrint` implementations
+macro_rules! impl_spore_parse_for_std_enums {
+    ($(
+        $name:literal: impl$(<$($param:ident),*>)? for $t:ty {
+            $($variant:literal $(($value:ident))? => $constructor:expr),* $(,)?
+        }
+    )*) => {
+        $(
+            impl$(<$($param),*>)? SporeParse for $t
+            where
+                $($($param: SporeParse,)*)?
+            {
+                fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+                    let (variant, fields) = take_variant(value, $name)?;
+                    match (variant.as_str(), fields) {
+                        $(($variant, variant_fields!($($value)?)) => {
+                            $(let $value = variant_field($value)?;)?
+                            Ok($constructor)
+                        })*
+                        _ => Err(SporeParseError::Mismatch {
+                            path: String::new(),
+                            expected: concat!("a variant of `", $name, "`").to_string(),
+                            found: format!("variant `{}::{}`", $name, variant),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_spore_parse_for_std_enums! {
+    "Bound": impl<T> for Bound<T> {
+        "Included"(value) => Bound::Included(value),
+        "Excluded"(value) => Bound::Excluded(value),
+        "Unbounded" => Bound::Unbounded,
+    }
+    "Ordering": impl for cmp::Ordering {
+        "Less" => cmp::Ordering::Less,
+        "Equal" => cmp::Ordering::Equal,
+        "Greater" => cmp::Ordering::Greater,
+    }
+    "ControlFlow": impl<B, C> for ControlFlow<B, C> {
+        "Continue"(value) => ControlFlow::Continue(value),
+        "Break"(value) => ControlFlow::Break(value),
+    }
+    "Poll": impl<T> for Poll<T> {
+        "Ready"(value) => Poll::Ready(value),
+        "Pending" => Poll::Pending,
+    }
+}
+
+impl<T: ?Sized> SporeParse for PhantomData<T> {
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::UnitStruct(name) if name == "PhantomData" => Ok(PhantomData),
+            other => Err(SporeParseError::mismatch("`PhantomData`", &other)),
+        }
+    }
+}
+
+impl<T> SporeParse for Reverse<T>
+where
+    T: SporeParse,
+{
+    fn from_spore_value(value: SporeValue) -> Result<Self, SporeParseError> {
+        match value {
+            SporeValue::TupleStruct { name, fields } if name == "Reverse" => {
+                variant_field(fields).map(Reverse)
+            }
+            other => Err(SporeParseError::mismatch("`Reverse(value)`", &other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that values round-trip through their spore print
+    #[test]
+    fn test_round_trip() {
+        fn round_trip<T: SporePrint + SporeParse + PartialEq + fmt::Debug>(value: T) {
+            let text = value.spore_print();
+            assert_eq!(T::spore_parse(&text).unwrap(), value, "{}", text);
+        }
+
+        round_trip(42u8);
+        round_trip(-7i64);
+        round_trip(usize::MAX);
+        round_trip(1.5f32);
+        round_trip(-0.25f64);
+        round_trip(3.0f64);
+        round_trip(true);
+        round_trip('\'');
+        round_trip("a \"quoted\"\nline".to_string());
+        round_trip(());
+        round_trip(Some(vec![Some(1), None]));
+        round_trip(Ok::<String, i32>("ok".to_string()));
+        round_trip(Err::<String, i32>(-1));
+        round_trip(HashMap::from([(1, "a".to_string()), (2, "b".to_string())]));
+        round_trip(HashSet::from(['x', 'y']));
+        round_trip([[1u16, 2], [3, 4]]);
+        round_trip((1,));
+        round_trip((1, 'a', "b".to_string(), (), [true]));
+        round_trip((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12));
+        round_trip(-5..5);
+        round_trip(Duration::new(90, 250_000_000));
+        round_trip(SystemTime::UNIX_EPOCH - Duration::new(1, 5));
+        round_trip(vec![SystemTime::now()]);
+        round_trip('a'..='z');
+        round_trip(3u8..);
+        round_trip(..-3);
+        round_trip(..=1.5);
+        round_trip(..);
+        round_trip(BTreeMap::from([(-1, vec!['a']), (10, vec![])]));
+        round_trip(BTreeSet::from([10, -2, 1]));
+        round_trip(VecDeque::from([1, 2]));
+        round_trip(LinkedList::from(["a".to_string()]));
+        round_trip(HashMap::<u8, bool, std::hash::RandomState>::from_iter([(
+            1, true,
+        )]));
+        round_trip(Box::new(1));
+        round_trip(Rc::new(Some(2)));
+        round_trip(Arc::new([3]));
+        round_trip(Box::<str>::from("boxed"));
+        round_trip(Rc::<[u8]>::from(vec![1, 2]));
+        round_trip(Cow::<str>::Owned("cow".to_string()));
+        round_trip(Cell::new(4));
+        round_trip(RefCell::new(vec![5]));
+        round_trip(NonZeroU8::new(7).unwrap());
+        round_trip(NonZeroI64::new(-7).unwrap());
+        round_trip(Wrapping(250u8));
+        round_trip(Saturating(-1i8));
+        round_trip(Bound::Included(1));
+        round_trip(Bound::<i32>::Unbounded);
+        round_trip(cmp::Ordering::Greater);
+        round_trip(ControlFlow::<i32, String>::Break(2));
+        round_trip(Poll::Ready(vec![Poll::<u8>::Pending]));
+        round_trip(PhantomData::<String>);
+        round_trip(Reverse((1, 'a')));
+        round_trip(OnceCell::from(8));
+        round_trip(OnceLock::<u8>::new());
+    }
+
+    /// Tests that cells and atomics, which do not implement `PartialEq`, read back the value they print
+    #[test]
+    fn test_parse_cells() {
+        let mutex = <Mutex<Vec<u8>>>::spore_parse("[1, 2]").unwrap();
+        assert_eq!(*mutex.lock().unwrap(), [1, 2]);
+        let lock = <RwLock<String>>::spore_parse(r#""a""#).unwrap();
+        assert_eq!(*lock.read().unwrap(), "a");
+        let atomic = AtomicI32::spore_parse("-3").unwrap();
+        assert_eq!(atomic.into_inner(), -3);
+        assert!(AtomicBool::spore_parse("true").unwrap().into_inner());
+        assert!(<OnceCell<u8>>::spore_parse("<uninit>")
+            .unwrap()
+            .get()
+            .is_none());
+    }
+
+    /// Tests that mismatched values are reported with their path
+    #[test]
+    fn test_mismatch_errors() {
+        let error = <Vec<Option<bool>>>::spore_parse("[Some(true), Some(1)]").unwrap_err();
+        assert_eq!(error.to_string(), "expected `bool`, found `1` at `[1]`");
+
+        let error = <HashMap<String, (u8, char)>>::spore_parse(r#"{"a": (1, "b")}"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"expected a char, found a string at `["a"][1]`"#
+        );
+
+        let error = <(u8, u8)>::spore_parse("(1, 2, 3)").unwrap_err();
+        assert_eq!(error.to_string(), "expected 2 items, found 3 items");
+
+        let error = <[u8; 2]>::spore_parse("[1]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a list of 2 items, found a list of 1 items"
+        );
+
+        let error = <RangeInclusive<u8>>::spore_parse("1..2").unwrap_err();
+        assert_eq!(error.to_string(), "expected a range `a..=b`, found a range");
+
+        let error = <RangeFrom<u8>>::spore_parse("..2").unwrap_err();
+        assert_eq!(error.to_string(), "expected a range `a..`, found a range");
+
+        let error = NonZeroU32::spore_parse("0").unwrap_err();
+        assert_eq!(error.to_string(), "expected a non-zero `u32`, found `0`");
+
+        let error = <Bound<u8>>::spore_parse("Bound::Included(300)").unwrap_err();
+        assert_eq!(error.to_string(), "expected `u8`, found `300` at `.0`");
+
+        let error = <Bound<u8>>::spore_parse("Bound::Unbounded(1)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a variant of `Bound`, found variant `Bound::Unbounded`"
+        );
+
+        let error = cmp::Ordering::spore_parse("Poll::Pending").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a variant of `Ordering`, found variant `Poll::Pending`"
+        );
+
+        let error = <BTreeMap<u8, u8>>::spore_parse("{1: -1}").unwrap_err();
+        assert_eq!(error.to_string(), "expected `u8`, found `-1` at `[1]`");
+    }
+
+    /// Tests that syntax errors keep their position
+    #[test]
+    fn test_syntax_error() {
+        let error = <Vec<u8>>::spore_parse("[1,\n2").unwrap_err();
+        match &error {
+            SporeParseError::Syntax(error) => assert_eq!((error.line(), error.column()), (2, 2)),
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(error.source().is_some());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{SporePrint, SporeValue};
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+const SECS_PER_DAY: i64 = 86_400;
+
+// Implement `SporePrint` for `Duration` in seconds, as in `0s`, `1.5s` or `0.000000001s`. The fraction keeps the
+// digits of the nanoseconds up to the last non-zero one, so every duration has exactly one exact print.
+impl SporePrint for Duration {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", self.as_secs())?;
+        write_fraction(out, self.subsec_nanos())?;
+        out.write_char('s')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Duration(*self)
+    }
+}
+
+// Implement `SporePrint` for `SystemTime` as an RFC 3339 timestamp in UTC, as in `2024-05-06T07:08:09.5Z`, with
+// the fraction of a second written like the one of a `Duration`
+impl SporePrint for SystemTime {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let (secs, nanos) = unix_time(self);
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+        if year < 0 {
+            out.write_char('-')?;
+        }
+        write!(
+            out,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year.unsigned_abs(),
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )?;
+        write_fraction(out, nanos)?;
+        out.write_char('Z')
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Timestamp(*self)
+    }
+}
+
+// Implement `SporePrint` for `Instant` as the marker `<instant>`. An instant is only meaningful relative to
+// another one of the same run, so printing it would make every print differ.
+impl SporePrint for Instant {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("<instant>")
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        SporeValue::Marker("instant".to_string())
+    }
+}
+
+// Helper function to write the fraction of a second after a `.`, without trailing zeros, if it is not zero
+fn write_fraction(out: &mut dyn fmt::Write, nanos: u32) -> fmt::Result {
+    if nanos == 0 {
+        return Ok(());
+    }
+    let digits = format!("{:09}", nanos);
+    write!(out, ".{}", digits.trim_end_matches('0'))
+}
+
+/// Parses the digits of a duration printed in seconds, such as `1.5` in `1.5s`.
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    let (secs, fraction) = match text.split_once('.') {
+        Some((secs, fraction)) => (secs, Some(fraction)),
+        None => (text, None),
+    };
+    if !is_digits(secs) {
+        return None;
+    }
+    let nanos = match fraction {
+        Some(fraction) => parse_fraction(fraction)?,
+        None => 0,
+    };
+    Some(Duration::new(secs.parse().ok()?, nanos))
+}
+
+/// Parses an RFC 3339 timestamp in UTC, such as `2024-05-06T07:08:09.5Z`.
+pub(crate) fn parse_timestamp(text: &str) -> Option<SystemTime> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (date, time) = text.strip_suffix('Z')?.split_once('T')?;
+    let (year, date) = date.split_once('-')?;
+    let (month, day) = date.split_once('-')?;
+    let (time, nanos) = match time.split_once('.') {
+        Some((time, fraction)) => (time, parse_fraction(fraction)?),
+        None => (time, 0),
+    };
+    let (hour, time) = time.split_once(':')?;
+    let (minute, second) = time.split_once(':')?;
+
+    if year.len() < 4 || !is_digits(year) {
+        return None;
+    }
+    let year: i64 = year.parse().ok()?;
+    let year = if negative { -year } else { year };
+    let [month, day, hour, minute, second] =
+        [month, day, hour, minute, second].map(|part| two_digits(part).unwrap_or(u32::MAX));
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let secs = days_from_civil(year, month, day)
+        .checked_mul(SECS_PER_DAY)?
+        .checked_add(i64::from(hour * 3600 + minute * 60 + second))?;
+    let whole = match u64::try_from(secs) {
+        Ok(secs) => UNIX_EPOCH.checked_add(Duration::from_secs(secs))?,
+        Err(_) => UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?,
+    };
+    whole.checked_add(Duration::from_nanos(u64::from(nanos)))
+}
+
+// Helper function to parse 1 to 9 digits of a fraction of a second into nanoseconds
+fn parse_fraction(fraction: &str) -> Option<u32> {
+    if fraction.is_empty() || fraction.len() > 9 || !is_digits(fraction) {
+        return None;
+    }
+    let nanos: u32 = fraction.parse().ok()?;
+    Some(nanos * 10u32.pow(9 - fraction.len() as u32))
+}
+
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn two_digits(text: &str) -> Option<u32> {
+    if text.len() == 2 && is_digits(text) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Splits `time` into whole seconds since the Unix epoch, negative before it, and the nanoseconds after them.
+fn unix_time(time: &SystemTime) -> (i64, u32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() as i64, since.subsec_nanos()),
+        Err(error) => {
+            let before = error.duration();
+            let secs = -(before.as_secs() as i64);
+            match before.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs - 1, NANOS_PER_SEC - nanos),
+            }
+        }
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian `(year, month, day)`.
+///
+/// This is the `civil_from_days` algorithm of Howard Hinnant, which works in 400-year eras starting on March 1st
+/// so that leap days fall at the end of a year.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
+
+/// Converts a proleptic Gregorian date into days since 1970-01-01, the inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(secs: i64, nanos: u32) -> SystemTime {
+        let time = match u64::try_from(secs) {
+            Ok(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+            Err(_) => UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()),
+        };
+        time + Duration::from_nanos(u64::from(nanos))
+    }
+
+    /// Tests the canonical print of durations
+    #[test]
+    fn test_duration() {
+        assert_eq!(Duration::ZERO.spore_print(), "0s");
+        assert_eq!(Duration::from_millis(1500).spore_print(), "1.5s");
+        assert_eq!(Duration::from_millis(250).spore_print(), "0.25s");
+        assert_eq!(Duration::from_nanos(1).spore_print(), "0.000000001s");
+        assert_eq!(Duration::from_secs(3600).spore_print(), "3600s");
+        assert_eq!(
+            Duration::MAX.spore_print(),
+            "18446744073709551615.999999999s"
+        );
+
+        for text in ["0", "1.5", "0.000000001", "18446744073709551615.999999999"] {
+            let duration = parse_duration(text).unwrap();
+            assert_eq!(duration.spore_print(), format!("{}s", text));
+        }
+        for text in [
+            "",
+            "1.",
+            ".5",
+            "1.0000000001",
+            "-1",
+            "1e3",
+            "18446744073709551616",
+        ] {
+            assert_eq!(parse_duration(text), None, "{}", text);
+        }
+    }
+
+    /// Tests that timestamps print as RFC 3339 in UTC, including before the epoch and around leap days
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(UNIX_EPOCH.spore_print(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            timestamp(1_715_000_000, 500_000_000).spore_print(),
+            "2024-05-06T12:53:20.5Z"
+        );
+        assert_eq!(
+            timestamp(951_782_400, 0).spore_print(),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(timestamp(-1, 0).spore_print(), "1969-12-31T23:59:59Z");
+        assert_eq!(
+            timestamp(-1, 999_999_999).spore_print(),
+            "1969-12-31T23:59:59.999999999Z"
+        );
+        assert_eq!(
+            timestamp(-62_167_219_200, 0).spore_print(),
+            "0000-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            timestamp(-62_198_755_200, 0).spore_print(),
+            "-0001-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            timestamp(253_402_300_800, 0).spore_print(),
+            "10000-01-01T00:00:00Z"
+        );
+    }
+
+    /// Tests that printed timestamps parse back to the same time, and that invalid ones are rejected
+    #[test]
+    fn test_parse_timestamp() {
+        for (secs, nanos) in [
+            (0, 0),
+            (1_715_000_000, 500_000_000),
+            (951_782_400, 1),
+            (-1, 999_999_999),
+            (-62_198_755_200, 0),
+            (253_402_300_800, 0),
+        ] {
+            let time = timestamp(secs, nanos);
+            assert_eq!(parse_timestamp(&time.spore_print()), Some(time));
+        }
+        assert_eq!(
+            parse_timestamp("2024-05-06T12:53:20.500Z"),
+            Some(timestamp(1_715_000_000, 500_000_000))
+        );
+        for text in [
+            "2023-02-29T00:00:00Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:60Z",
+            "2024-01-01T00:00:00",
+            "2024-01-01T00:00:00+01:00",
+            "2024-1-01T00:00:00Z",
+            "24-01-01T00:00:00Z",
+            "2024-01-01T00:00:00.Z",
+            "2024-01-01",
+        ] {
+            assert_eq!(parse_timestamp(text), None, "{}", text);
+        }
+    }
+
+    /// Tests that instants print as a marker
+    #[test]
+    fn test_instant() {
+        assert_eq!(Instant::now().spore_print(), "<instant>");
+        assert_eq!(
+            Instant::now().to_spore_value(),
+            SporeValue::Marker("instant".to_string())
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{write_items, write_quoted, SporePrint};
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// An owned, structural representation of a spore print.
+///
+/// Every piece of syntax in the spore print grammar maps to one variant, so a `SporeValue` can be inspected
+/// without splitting strings. Struct-like values keep the type name that was printed; enum variants wrap a
+/// [`UnitStruct`](SporeValue::UnitStruct), [`TupleStruct`](SporeValue::TupleStruct) or
+/// [`Struct`](SporeValue::Struct) named after the variant.
+///
+/// A `SporeValue` is itself `SporePrint`: printing it is the text backend, and it prints exactly the text of the
+/// value it was obtained from with [`SporePrint::to_spore_value`].
+///
+/// # Examples
+///
+/// ```
+/// use spore_print::SporeValue;
+///
+/// let value: SporeValue = "Shape::Circle { radius: 2 }".parse().unwrap();
+/// assert_eq!(
+///     value,
+///     SporeValue::Variant {
+///         enum_name: "Shape".to_string(),
+///         variant: Box::new(SporeValue::Struct {
+///             name: "Circle".to_string(),
+///             fields: vec![("radius".to_string(), SporeValue::Int(2))],
+///         }),
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SporeValue {
+    /// The unit value `()`.
+    Unit,
+    /// `true` or `false`.
+    Bool(bool),
+    /// An integer such as `-42`.
+    Int(i128),
+    /// An integer too large for [`Int`](SporeValue::Int), i.e. above `i128::MAX`.
+    UInt(u128),
+    /// A floating-point number such as `1.5`, `NaN` or `-inf`.
+    Float(f64),
+    /// A duration in seconds such as `1.5s`.
+    Duration(Duration),
+    /// A point in time printed as an RFC 3339 timestamp in UTC, such as `2024-05-06T07:08:09.5Z`.
+    Timestamp(SystemTime),
+    /// A double-quoted string, unescaped.
+    Str(String),
+    /// A single-quoted char, unescaped.
+    Char(char),
+    /// A list `[a, b]`, printed for vectors, slices, arrays and sets.
+    List(Vec<SporeValue>),
+    /// A map `{k: v}`, with entries in printed order.
+    Map(Vec<(SporeValue, SporeValue)>),
+    /// A tuple `(a, b)` or `(a,)`.
+    Tuple(Vec<SporeValue>),
+    /// `Some(value)` or `None`.
+    Option(Option<Box<SporeValue>>),
+    /// `Ok(value)` or `Err(error)`.
+    Result(Result<Box<SporeValue>, Box<SporeValue>>),
+    /// A range `start..end`, or `start..=end` when `inclusive`. Either bound may be absent, as in `start..`.
+    Range {
+        start: Option<Box<SporeValue>>,
+        end: Option<Box<SporeValue>>,
+        inclusive: bool,
+    },
+    /// A unit struct `Name`.
+    UnitStruct(String),
+    /// A struct with named fields `Name { field: value }`.
+    Struct {
+        name: String,
+        fields: Vec<(String, SporeValue)>,
+    },
+    /// A tuple struct `Name(a, b)`.
+    TupleStruct {
+        name: String,
+        fields: Vec<SporeValue>,
+    },
+    /// An enum variant `Enum::Variant`, `Enum::Variant(..)` or `Enum::Variant { .. }`.
+    Variant {
+        enum_name: String,
+        variant: Box<SporeValue>,
+    },
+    /// A marker `<text>` standing in for a value that is deliberately not printed, such as `<redacted>`.
+    Marker(String),
+    /// Text that does not follow the spore print grammar, printed verbatim. Produced by the default
+    /// [`SporePrint::to_spore_value`] for hand-written implementations whose output cannot be parsed.
+    Raw(String),
+}
+
+impl SporePrint for SporeValue {
+    fn spore_print_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            SporeValue::Unit => out.write_str("()"),
+            SporeValue::Bool(value) => value.spore_print_to(out),
+            SporeValue::Int(value) => write!(out, "{}", value),
+            SporeValue::UInt(value) => write!(out, "{}", value),
+            SporeValue::Float(value) => value.spore_print_to(out),
+            SporeValue::Duration(value) => value.spore_print_to(out),
+            SporeValue::Timestamp(value) => value.spore_print_to(out),
+            SporeValue::Str(value) => write_quoted(out, value, '"'),
+            SporeValue::Char(value) => value.spore_print_to(out),
+            SporeValue::List(items) => write_items(out, "[", items, "]"),
+            SporeValue::Map(entries) => {
+                out.write_char('{')?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        out.write_str(", ")?;
+                    }
+                    key.spore_print_to(out)?;
+                    out.write_str(": ")?;
+                    value.spore_print_to(out)?;
+                }
+                out.write_char('}')
+            }
+            SporeValue::Tuple(items) if items.len() == 1 => write_items(out, "(", items, ",)"),
+            SporeValue::Tuple(items) => write_items(out, "(", items, ")"),
+            SporeValue::Option(Some(value)) => write_items(out, "Some(", [&**value], ")"),
+            SporeValue::Option(None) => out.write_str("None"),
+            SporeValue::Result(Ok(value)) => write_items(out, "Ok(", [&**value], ")"),
+            SporeValue::Result(Err(err)) => write_items(out, "Err(", [&**err], ")"),
+            SporeValue::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if let Some(start) = start {
+                    start.spore_print_to(out)?;
+                }
+                out.write_str(if *inclusive { "..=" } else { ".." })?;
+                match end {
+                    Some(end) => end.spore_print_to(out),
+                    None => Ok(()),
+                }
+            }
+            SporeValue::UnitStruct(name) => out.write_str(name),
+            SporeValue::Struct { name, fields } => {
+                write!(out, "{} {{ ", name)?;
+                for (index, (field, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        out.write_str(", ")?;
+                    }
+                    write!(out, "{}: ", field)?;
+                    value.spore_print_to(out)?;
+                }
+                out.write_str(" }")
+            }
+            SporeValue::TupleStruct { name, fields } => {
+                out.write_str(name)?;
+                write_items(out, "(", fields, ")")
+            }
+            SporeValue::Variant { enum_name, variant } => {
+                write!(out, "{}::", enum_name)?;
+                variant.spore_print_to(out)
+            }
+            SporeValue::Marker(text) => write!(out, "<{}>", text),
+            SporeValue::Raw(text) => out.write_str(text),
+        }
+    }
+
+    fn to_spore_value(&self) -> SporeValue {
+        self.clone()
+    }
+}
+
+impl SporeValue {
+    /// Compares two values structurally, for printing hashed collections in a canonical order.
+    ///
+    /// The order follows `Ord` for the standard types: numbers compare numerically, strings and chars by code
+    /// point, and lists, tuples, maps and structs item by item. Values of different kinds, which only meet
+    /// inside user types, are ordered by kind. The order is total, and equal only for values that print the
+    /// same, apart from NaNs of different sign or payload, which also print the same.
+    pub(crate) fn canonical_cmp(&self, other: &SporeValue) -> Ordering {
+        use SporeValue::*;
+
+        match (self, other) {
+            (Bool(a), Bool(b)) => a.cmp(b),
+            (Int(_) | UInt(_) | Float(_), Int(_) | UInt(_) | Float(_)) => {
+                compare_numbers(self, other)
+            }
+            (Duration(a), Duration(b)) => a.cmp(b),
+            (Timestamp(a), Timestamp(b)) => a.cmp(b),
+            (Char(a), Char(b)) => a.cmp(b),
+            (Str(a), Str(b)) | (Marker(a), Marker(b)) | (Raw(a), Raw(b)) => a.cmp(b),
+            (List(a), List(b)) | (Tuple(a), Tuple(b)) => {
+                compare_items(a, b, SporeValue::canonical_cmp)
+            }
+            (Map(a), Map(b)) => compare_items(a, b, |(key_a, value_a), (key_b, value_b)| {
+                key_a
+                    .canonical_cmp(key_b)
+                    .then_with(|| value_a.canonical_cmp(value_b))
+            }),
+            (Option(a), Option(b)) => compare_options(a.as_deref(), b.as_deref()),
+            (Result(a), Result(b)) => match (a, b) {
+                (Ok(a), Ok(b)) | (Err(a), Err(b)) => a.canonical_cmp(b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+            },
+            (
+                Range {
+                    start: start_a,
+                    end: end_a,
+                    inclusive: inclusive_a,
+                },
+                Range {
+                    start: start_b,
+                    end: end_b,
+                    inclusive: inclusive_b,
+                },
+            ) => compare_options(start_a.as_deref(), start_b.as_deref())
+                .then_with(|| compare_options(end_a.as_deref(), end_b.as_deref()))
+                .then_with(|| inclusive_a.cmp(inclusive_b)),
+            (UnitStruct(a), UnitStruct(b)) => a.cmp(b),
+            (
+                Struct {
+                    name: name_a,
+                    fields: fields_a,
+                },
+                Struct {
+                    name: name_b,
+                    fields: fields_b,
+                },
+            ) => name_a.cmp(name_b).then_with(|| {
+                compare_items(
+                    fields_a,
+                    fields_b,
+                    |(field_a, value_a), (field_b, value_b)| {
+                        field_a
+                            .cmp(field_b)
+                            .then_with(|| value_a.canonical_cmp(value_b))
+                    },
+                )
+            }),
+            (
+                TupleStruct {
+                    name: name_a,
+                    fields: fields_a,
+                },
+                TupleStruct {
+                    name: name_b,
+                    fields: fields_b,
+                },
+            ) => name_a
+                .cmp(name_b)
+                .then_with(|| compare_items(fields_a, fields_b, SporeValue::canonical_cmp)),
+            (
+                Variant {
+                    enum_name: enum_a,
+                    variant: variant_a,
+                },
+                Variant {
+                    enum_name: enum_b,
+                    variant: variant_b,
+                },
+            ) => enum_a
+                .cmp(enum_b)
+                .then_with(|| variant_a.canonical_cmp(variant_b)),
+            _ => self.kind_rank().cmp(&other.kind_rank()),
+        }
+    }
+
+    /// Returns the position of the kind of `self` in the order of values of different kinds.
+    fn kind_rank(&self) -> u8 {
+        match self {
+            SporeValue::Unit => 0,
+            SporeValue::Bool(_) => 1,
+            SporeValue::Int(_) | SporeValue::UInt(_) | SporeValue::Float(_) => 2,
+            SporeValue::Duration(_) => 3,
+            SporeValue::Timestamp(_) => 4,
+            SporeValue::Char(_) => 5,
+            SporeValue::Str(_) => 6,
+            SporeValue::List(_) => 7,
+            SporeValue::Map(_) => 8,
+            SporeValue::Tuple(_) => 9,
+            SporeValue::Option(_) => 10,
+            SporeValue::Result(_) => 11,
+            SporeValue::Range { .. } => 12,
+            SporeValue::UnitStruct(_) => 13,
+            SporeValue::Struct { .. } => 14,
+            SporeValue::TupleStruct { .. } => 15,
+            SporeValue::Variant { .. } => 16,
+            SporeValue::Marker(_) => 17,
+            SporeValue::Raw(_) => 18,
+        }
+    }
+}
+
+/// Compares two numbers by their value as an `f64`, then, when that rounds them to the same float, puts the
+/// float first and compares integers exactly. Rounding to `f64` never reverses two integers, so the order is
+/// total.
+fn compare_numbers(a: &SporeValue, b: &SporeValue) -> Ordering {
+    // Each number as an `f64`, and as a sign and magnitude when it is an integer.
+    let parts = |value: &SporeValue| match *value {
+        SporeValue::Int(int) => (int as f64, Some((int >= 0, int.unsigned_abs()))),
+        SporeValue::UInt(int) => (int as f64, Some((true, int))),
+        SporeValue::Float(float) => (float, None),
+        _ => unreachable!("only numbers are compared as numbers"),
+    };
+    let (float_a, int_a) = parts(a);
+    let (float_b, int_b) = parts(b);
+    float_a
+        .total_cmp(&float_b)
+        .then_with(|| match (int_a, int_b) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some((true, a)), Some((true, b))) => a.cmp(&b),
+            (Some((false, a)), Some((false, b))) => b.cmp(&a),
+            (Some((positive_a, _)), Some((positive_b, _))) => positive_a.cmp(&positive_b),
+        })
+}
+
+/// Compares two sequences item by item, then by length.
+fn compare_items<T>(a: &[T], b: &[T], compare: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| compare(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+/// Compares two optional values, with `None` first.
+fn compare_options(a: Option<&SporeValue>, b: Option<&SporeValue>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.canonical_cmp(b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+/// Builds the `SporeValue` of a field printed by a `#[spore(with = "...")]` function, by parsing its output.
+#[doc(hidden)]
+pub fn value_with(write: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result) -> SporeValue {
+    let mut text = String::new();
+    write(&mut text).expect("writing a spore print to a String cannot fail");
+    crate::parse::parse(&text).unwrap_or(SporeValue::Raw(text))
+}
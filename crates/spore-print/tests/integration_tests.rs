@@ -1,4 +1,5 @@
-use spore_print::{sprint, SporePrint};
+use spore_print::parse::parse;
+use spore_print::{sformat, SporePrint, SporeValue};
 use spore_print_derive::SporePrint;
 use std::collections::{HashMap, HashSet};
 use std::ops::Range;
@@ -37,18 +38,14 @@ fn test_deeply_nested_vec_option() {
 /// Tests `HashMap` with `SporePrint` to ensure the `spore_print` method correctly represents the map.
 #[test]
 fn test_hashmap_membership() {
-    let map: HashMap<&str, i32> = [("key1", 1), ("key2", 2)].iter().cloned().collect();
-
-    let expected: HashSet<String> =
-        HashSet::from_iter(vec!["key1: 1".to_string(), "key2: 2".to_string()]);
-    let actual: HashSet<String> = HashSet::from_iter(
-        map.spore_print()
-            .trim_matches(|c| c == '{' || c == '}')
-            .split(", ")
-            .map(|s| s.to_string()),
-    );
+    let map: HashMap<&str, i32> = [("key2", 2), ("key1", 1)].iter().cloned().collect();
+    assert_eq!(map.spore_print(), r#"{"key1": 1, "key2": 2}"#);
 
-    assert_eq!(actual, expected);
+    let map: HashMap<i64, &str> = HashMap::from([(10, "ten"), (-3, "minus three"), (2, "two")]);
+    assert_eq!(
+        map.spore_print(),
+        r#"{-3: "minus three", 2: "two", 10: "ten"}"#
+    );
 }
 
 /// Tests `Range<usize>` with `SporePrint` to ensure the `spore_print` method correctly represents the range.
@@ -69,35 +66,34 @@ fn test_range_f32() {
 #[test]
 fn test_range_string() {
     let range = "a".to_string().."z".to_string();
-    assert_eq!(range.spore_print(), "a..z");
+    assert_eq!(range.spore_print(), r#""a".."z""#);
 }
 
 /// Tests tuple with `SporePrint` to ensure the `spore_print` method correctly represents the tuple.
 #[test]
 fn test_tuple() {
     let tuple = (42, "hello", Some(std::f64::consts::PI));
-    assert_eq!(tuple.spore_print(), "(42, hello, Some(3.141592653589793))");
+    assert_eq!(
+        tuple.spore_print(),
+        r#"(42, "hello", Some(3.141592653589793))"#
+    );
 }
 
 /// Tests vector of strings with `SporePrint` to ensure the `spore_print` method correctly represents the vector.
 #[test]
 fn test_vec_of_strings() {
     let vec = vec!["one".to_string(), "two".to_string(), "three".to_string()];
-    assert_eq!(vec.spore_print(), "[one, two, three]");
+    assert_eq!(vec.spore_print(), r#"["one", "two", "three"]"#);
 }
 
 /// Tests `HashSet` with `SporePrint` to ensure the `spore_print` method correctly represents the set.
 #[test]
 fn test_hashset() {
-    let set: HashSet<i32> = HashSet::from([1, 2]);
-    let expected: HashSet<String> = HashSet::from_iter(vec!["1".to_string(), "2".to_string()]);
-    let actual: HashSet<String> = HashSet::from_iter(
-        set.spore_print()
-            .trim_matches(|c| c == '[' || c == ']')
-            .split(", ")
-            .map(|s| s.to_string()),
-    );
-    assert_eq!(actual, expected);
+    let set: HashSet<i32> = HashSet::from([2, 1]);
+    assert_eq!(set.spore_print(), "[1, 2]");
+
+    let set: HashSet<i32> = HashSet::from([2, 10, 1, -1, -20]);
+    assert_eq!(set.spore_print(), "[-20, -1, 1, 2, 10]");
 }
 
 /// A struct with primitive types.
@@ -140,7 +136,7 @@ fn test_complex_struct() {
     };
     assert_eq!(
         test_struct.spore_print(),
-        "ComplexStruct { ref_field: Hello, vec_field: [1, 2, 3], option_field: Some(World) }"
+        r#"ComplexStruct { ref_field: "Hello", vec_field: [1, 2, 3], option_field: Some("World") }"#
     );
 }
 
@@ -174,7 +170,7 @@ fn test_named_enum() {
     };
     assert_eq!(
         variant.spore_print(),
-        "NamedEnum::VariantX { id: 1, name: Alice }"
+        r#"NamedEnum::VariantX { id: 1, name: "Alice" }"#
     );
 }
 
@@ -189,7 +185,10 @@ enum UnnamedEnum {
 #[test]
 fn test_unnamed_enum() {
     let variant = UnnamedEnum::Variant1(42, "Answer".to_string());
-    assert_eq!(variant.spore_print(), "UnnamedEnum::Variant1(42, Answer)");
+    assert_eq!(
+        variant.spore_print(),
+        r#"UnnamedEnum::Variant1(42, "Answer")"#
+    );
 }
 
 /// An edge case: Empty struct.
@@ -299,7 +298,7 @@ fn test_result_struct() {
     };
     assert_eq!(
         test_struct_err.spore_print(),
-        "ResultStruct { result_field: Err(error) }"
+        r#"ResultStruct { result_field: Err("error") }"#
     );
 }
 
@@ -355,7 +354,7 @@ fn test_generic_range_struct_string() {
     };
     assert_eq!(
         test_struct.spore_print(),
-        "GenericRangeStruct { range_field: a..z }"
+        r#"GenericRangeStruct { range_field: "a".."z" }"#
     );
 }
 
@@ -373,7 +372,7 @@ fn test_tuple_struct() {
     };
     assert_eq!(
         test_struct.spore_print(),
-        "TupleStruct { tuple_field: (42, hello, Some(3.141592653589793)) }"
+        r#"TupleStruct { tuple_field: (42, "hello", Some(3.141592653589793)) }"#
     );
 }
 
@@ -391,7 +390,7 @@ fn test_vec_struct() {
     };
     assert_eq!(
         test_struct.spore_print(),
-        "VecStruct { vec_field: [one, two, three] }"
+        r#"VecStruct { vec_field: ["one", "two", "three"] }"#
     );
 }
 
@@ -405,18 +404,33 @@ struct HashSetStruct {
 #[test]
 fn test_hashset_struct() {
     let test_struct = HashSetStruct {
-        set_field: HashSet::from([1, 2]),
+        set_field: HashSet::from([2, 1]),
     };
-    let expected: HashSet<String> = HashSet::from_iter(vec!["1".to_string(), "2".to_string()]);
-    let actual: HashSet<String> = HashSet::from_iter(
-        test_struct
-            .spore_print()
-            .trim_start_matches("HashSetStruct { set_field: [")
-            .trim_end_matches("] }")
-            .split(", ")
-            .map(|s| s.trim().to_string()),
+    assert_eq!(
+        test_struct.spore_print(),
+        "HashSetStruct { set_field: [1, 2] }"
+    );
+}
+
+/// Tests that derived structs quote and escape string and char fields.
+#[test]
+fn test_derived_struct_strings_are_unambiguous() {
+    #[derive(SporePrint)]
+    struct Note {
+        title: String,
+        tags: Vec<&'static str>,
+        marker: char,
+    }
+
+    let note = Note {
+        title: "a \"quoted\"\ntitle".to_string(),
+        tags: vec!["a, b"],
+        marker: '\'',
+    };
+    assert_eq!(
+        note.spore_print(),
+        r#"Note { title: "a \"quoted\"\ntitle", tags: ["a, b"], marker: '\'' }"#
     );
-    assert_eq!(actual, expected);
 }
 
 #[derive(SporePrint)]
@@ -434,7 +448,7 @@ fn test_struct_spore_print() {
     };
     assert_eq!(
         instance.spore_print(),
-        "TestStruct { field1: 42, field2: hello }"
+        r#"TestStruct { field1: 42, field2: "hello" }"#
     );
 }
 /// An enum with unit variants.
@@ -449,16 +463,56 @@ enum TestEnum {
 #[test]
 fn test_enum_spore_print() {
     let instance1 = TestEnum::Variant1;
-    let sprint_value1 = sprint!(instance1);
+    let sprint_value1 = sformat!("{}", instance1);
     assert_eq!(sprint_value1, "TestEnum::Variant1");
 
     let instance2 = TestEnum::Variant2(42);
-    let sprint_value2 = sprint!(instance2);
+    let sprint_value2 = sformat!("{}", instance2);
     assert_eq!(sprint_value2, "TestEnum::Variant2(42)");
 
     let instance3 = TestEnum::Variant3 {
         field: "hello".to_string(),
     };
-    let sprint_value3 = sprint!(instance3);
-    assert_eq!(sprint_value3, "TestEnum::Variant3 { field: hello }");
+    let sprint_value3 = sformat!("{}", instance3);
+    assert_eq!(sprint_value3, r#"TestEnum::Variant3 { field: "hello" }"#);
+}
+
+/// Tests that the spore print of derived types parses back into a matching value tree.
+#[test]
+fn test_parse_derived_output() {
+    let instance = ComplexStruct {
+        ref_field: "Hello, world",
+        vec_field: vec![1, 2],
+        option_field: None,
+    };
+    let value = parse(&instance.spore_print()).unwrap();
+    assert_eq!(
+        value,
+        SporeValue::Struct {
+            name: "ComplexStruct".to_string(),
+            fields: vec![
+                (
+                    "ref_field".to_string(),
+                    SporeValue::Str("Hello, world".to_string())
+                ),
+                (
+                    "vec_field".to_string(),
+                    SporeValue::List(vec![SporeValue::Int(1), SporeValue::Int(2)])
+                ),
+                ("option_field".to_string(), SporeValue::Option(None)),
+            ],
+        }
+    );
+
+    let value = parse(&TestEnum::Variant2(-3).spore_print()).unwrap();
+    assert_eq!(
+        value,
+        SporeValue::Variant {
+            enum_name: "TestEnum".to_string(),
+            variant: Box::new(SporeValue::TupleStruct {
+                name: "Variant2".to_string(),
+                fields: vec![SporeValue::Int(-3)],
+            }),
+        }
+    );
 }